@@ -0,0 +1,219 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use common::{cli, Puzzle};
+
+/// The system allocator, counting every allocation so a benchmark can report them.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocation_counts() -> (usize, usize) {
+    (ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED_BYTES.load(Ordering::Relaxed))
+}
+
+struct BenchOptions {
+    runs: usize,
+    save: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    /// The directory holding the day directories.
+    dir: String,
+    days: Vec<String>,
+}
+
+/// The workspace this binary was built from, where the day directories live.
+const WORKSPACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+fn parse_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions{
+        runs: 10,
+        save: None,
+        baseline: None,
+        threshold: 10.0,
+        dir: WORKSPACE.to_string(),
+        days: vec![],
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let value = iter.next().ok_or("--runs requires a value")?;
+                options.runs = match value.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid run count: {}", value)),
+                };
+            },
+            "--save" => options.save = Some(iter.next().ok_or("--save requires a file")?.clone()),
+            "--baseline" => options.baseline = Some(iter.next().ok_or("--baseline requires a file")?.clone()),
+            "--dir" => options.dir = iter.next().ok_or("--dir requires a directory")?.clone(),
+            "--threshold" => {
+                let value = iter.next().ok_or("--threshold requires a value")?;
+                options.threshold = common::parse_param(value, "threshold")?;
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ if ::puzzle(arg).is_none() => return Err(format!("Unknown day: {}", arg)),
+            _ => options.days.push(arg.clone()),
+        }
+    }
+
+    if options.days.is_empty() {
+        options.days = (1..).
+            map(|n| format!("day{}", n)).
+            take_while(|day| ::puzzle(day).is_some()).
+            collect();
+    }
+
+    Ok(options)
+}
+
+/// Wall time and allocations for one part over every run.
+struct Measurement {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+fn measure(puzzle: &dyn Puzzle, input: &str, part: u32, runs: usize) -> Result<Measurement, String> {
+    let mut times = Vec::with_capacity(runs);
+    let mut allocations = 0;
+    let mut bytes = 0;
+
+    for _ in 0..runs {
+        let (allocations_before, bytes_before) = allocation_counts();
+        let start = Instant::now();
+        let answer = puzzle.solve(input, part).map_err(|e| e.to_string())?;
+        times.push(start.elapsed());
+
+        let (allocations_after, bytes_after) = allocation_counts();
+        allocations = allocations_after - allocations_before;
+        bytes = bytes_after - bytes_before;
+        drop(answer);
+    }
+
+    times.sort();
+    Ok(Measurement{
+        min: times[0],
+        median: times[times.len() / 2],
+        max: times[times.len() - 1],
+        allocations,
+        bytes,
+    })
+}
+
+/// Reads the input a day is benchmarked on: its puzzle input, or its test fixture.
+fn read_bench_input(dir: &str, day: &str) -> Result<String, String> {
+    for file in &["input.dat", "test.dat"] {
+        let path = Path::new(dir).join(day).join(file);
+        if path.exists() {
+            return cli::read_input(&path.to_string_lossy());
+        }
+    }
+    Err(format!("{}: no input.dat or test.dat to benchmark in {}", day, Path::new(dir).join(day).display()))
+}
+
+/// Reads `<day> <part> <median ns>` lines written by `--save`.
+fn read_baseline(path: &str) -> Result<HashMap<(String, u32), u64>, String> {
+    let mut baseline = HashMap::new();
+    for (i, line) in cli::read_input(path)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || format!("{}: line {}: expected <day> <part> <median ns>, found \"{}\"", path, i + 1, line);
+        if fields.len() != 3 {
+            return Err(invalid());
+        }
+        let part = fields[1].parse().map_err(|_| invalid())?;
+        let median = fields[2].parse().map_err(|_| invalid())?;
+        baseline.insert((fields[0].to_string(), part), median);
+    }
+    Ok(baseline)
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Benchmarks each day's parts, printing a row per part as it completes.
+pub fn run(args: &[String]) -> Result<String, cli::Error> {
+    let options = parse_options(args).map_err(cli::Error::Usage)?;
+    let baseline = match options.baseline {
+        Some(ref path) => Some(read_baseline(path).map_err(cli::Error::Input)?),
+        None => None,
+    };
+
+    println!("{:<6} {:>4} {:>12} {:>12} {:>12} {:>10} {:>12}  vs baseline",
+        "day", "part", "min", "median", "max", "allocs", "bytes");
+
+    let mut saved = String::from("# day part median_ns\n");
+    let mut regressions = 0;
+    for day in &options.days {
+        let puzzle = ::puzzle(day).ok_or_else(|| cli::Error::Usage(format!("Unknown day: {}", day)))?;
+        let input = read_bench_input(&options.dir, day).map_err(cli::Error::Input)?;
+
+        for part in 1..=2 {
+            let m = measure(&*puzzle, &input, part, options.runs).
+                map_err(|e| cli::Error::Input(format!("{}: {}", day, e)))?;
+            let median_ns = m.median.as_secs_f64() * 1e9;
+            saved.push_str(&format!("{} {} {}\n", day, part, median_ns.round() as u64));
+
+            let comparison = match baseline.as_ref().and_then(|b| b.get(&(day.clone(), part))) {
+                Some(&base_ns) if base_ns > 0 => {
+                    let change = (median_ns / base_ns as f64 - 1.0) * 100.0;
+                    if change > options.threshold {
+                        regressions += 1;
+                        format!("{:+.1}% REGRESSION", change)
+                    } else {
+                        format!("{:+.1}%", change)
+                    }
+                },
+                _ => "-".to_string(),
+            };
+
+            println!("{:<6} {:>4} {:>12} {:>12} {:>12} {:>10} {:>12}  {}",
+                day, part, millis(m.min), millis(m.median), millis(m.max), m.allocations, m.bytes, comparison);
+        }
+    }
+
+    if let Some(ref path) = options.save {
+        fs::write(path, saved).map_err(|e| cli::Error::Input(format!("{}: {}", path, e)))?;
+    }
+
+    if regressions > 0 {
+        return Err(cli::Error::Input(format!("{} part(s) slower than the baseline by more than {}%",
+            regressions, options.threshold)));
+    }
+    Ok(format!("{} day(s), {} run(s) of each part", options.days.len(), options.runs))
+}
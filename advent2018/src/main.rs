@@ -0,0 +1,103 @@
+extern crate common;
+extern crate day1;
+extern crate day2;
+extern crate day3;
+extern crate day4;
+extern crate day5;
+extern crate day6;
+extern crate day7;
+extern crate day8;
+extern crate day9;
+extern crate day10;
+extern crate day11;
+extern crate day12;
+extern crate day13;
+extern crate day14;
+extern crate day15;
+extern crate day16;
+extern crate day17;
+
+mod bench;
+
+use std::env;
+
+use common::{cli, Puzzle};
+
+const USAGE: &str = "\
+Usage: advent2018 <day> [options] <input>...
+       advent2018 bench [bench options] [<day>...]
+
+Arguments:
+    <day>              Puzzle to solve, e.g. day15
+    <input>...         Puzzle input files, read in order (- reads stdin)
+
+Day-specific parameters (--param):
+    day1               file to write the part 2 frequency trace to as CSV
+    day2               checksum multiplicities, comma separated (2,3),
+                       part 2 matching, hamming or levenshtein (hamming)
+    day3               file to save the part 1 fabric heatmap to (.png, else PPM)
+    day4               strategies for part 1 and part 2 (most-asleep, most-consistent,
+                       longest-nap), and timeline to chart every shift
+    day5               part 2 thread count (available parallelism), and a reaction
+                       rule file with lines xy or xy -> z (opposite case letters)
+    day6               max total distance (10000), part 1 metric, manhattan, chebyshev
+                       or euclidean (manhattan)
+    day7               worker count (5), base step duration (60)
+    day12              generation count (20 for part 1, 50000000000 for part 2)
+
+Bench options (each day reads <day>/input.dat, or <day>/test.dat without one):
+    --dir <dir>        Directory holding the day directories (the workspace built from)
+    --runs <n>         Repetitions of each part (default 10)
+    --save <file>      Write the median times to a baseline file
+    --baseline <file>  Flag parts whose median is slower than in the baseline
+    --threshold <pct>  Slowdown tolerated against the baseline (default 10)";
+
+/// The solver for `day`, e.g. "day15".
+fn puzzle(day: &str) -> Option<Box<dyn Puzzle>> {
+    let puzzle: Box<dyn Puzzle> = match day {
+        "day1" => Box::new(day1::Day1::default()),
+        "day2" => Box::new(day2::Day2::default()),
+        "day3" => Box::new(day3::Day3::default()),
+        "day4" => Box::new(day4::Day4::default()),
+        "day5" => Box::new(day5::Day5::default()),
+        "day6" => Box::new(day6::Day6::default()),
+        "day7" => Box::new(day7::Day7::default()),
+        "day8" => Box::new(day8::Day8),
+        "day9" => Box::new(day9::Day9),
+        "day10" => Box::new(day10::Day10),
+        "day11" => Box::new(day11::Day11),
+        "day12" => Box::new(day12::Day12::default()),
+        "day13" => Box::new(day13::Day13),
+        "day14" => Box::new(day14::Day14),
+        "day15" => Box::new(day15::Day15),
+        "day16" => Box::new(day16::Day16),
+        "day17" => Box::new(day17::Day17),
+        _ => return None,
+    };
+    Some(puzzle)
+}
+
+fn run(command: &str, args: &[String]) -> Result<String, cli::Error> {
+    if command == "bench" {
+        return bench::run(args);
+    }
+
+    match puzzle(command) {
+        Some(mut puzzle) => puzzle.run(args),
+        None => Err(cli::Error::Usage(format!("Unknown day: {}", command))),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let usage = format!("{}\n\n{}", USAGE, cli::OPTIONS);
+    if args.is_empty() || cli::is_help(&args) {
+        println!("{}", usage);
+        return;
+    }
+
+    match run(&args[0], &args[1..]) {
+        Ok(answer) => println!("{}", answer),
+        Err(error) => cli::exit_with_error(error, &usage),
+    }
+}
@@ -0,0 +1,186 @@
+use std::env;
+use std::io;
+use std::io::Read;
+use std::io::BufReader;
+use std::fs::File;
+use std::process;
+use std::time::Instant;
+
+use {Solver, ParseError, Json};
+
+pub const OPTIONS: &str = "\
+Options:
+    --part <n>         Part of the puzzle to solve (default 1)
+    --param <value>    Day-specific parameter, may be repeated
+    --format <format>  Output format: text or json (default text)
+    -h, --help         Print this message";
+
+/// Why a run failed: bad command line arguments, or input that could not be read or parsed.
+pub enum Error {
+    Usage(String),
+    Input(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+pub struct Options {
+    pub part: u32,
+    pub params: Vec<String>,
+    pub format: Format,
+    pub inputs: Vec<String>,
+}
+
+pub fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut part = 1;
+    let mut params = vec![];
+    let mut format = Format::Text;
+    let mut inputs = vec![];
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = iter.next().ok_or("--part requires a value")?;
+                part = match value.parse() {
+                    Ok(p @ 1..=2) => p,
+                    _ => return Err(format!("Invalid part: {}", value)),
+                };
+            },
+            "--param" => {
+                let value = iter.next().ok_or("--param requires a value")?;
+                params.push(value.clone());
+            },
+            "--format" => {
+                let value = iter.next().ok_or("--format requires a value")?;
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("Invalid format: {}", value)),
+                };
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ => inputs.push(arg.clone()),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err("Missing input".to_string());
+    }
+
+    Ok(Options{part, params, format, inputs})
+}
+
+/// Reads one input file, or standard input when `path` is `-`.
+pub fn read_input(path: &str) -> Result<String, String> {
+    let mut s = String::new();
+    if path == "-" {
+        io::stdin().read_to_string(&mut s).map_err(|e| format!("<stdin>: {}", e))?;
+    } else {
+        let f = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        let mut reader = BufReader::new(&f);
+        reader.read_to_string(&mut s).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(s)
+}
+
+/// Puzzle input concatenated from several files, remembering where each one starts.
+pub struct Input {
+    pub text: String,
+    starts: Vec<(String, usize)>,
+}
+
+impl Input {
+    /// Reads `paths` in order, joining them so that every file starts on a new line.
+    pub fn read(paths: &[String]) -> Result<Input, String> {
+        let mut text = String::new();
+        let mut starts = vec![];
+
+        for path in paths {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            let name = if path == "-" { "<stdin>" } else { path.as_str() };
+            starts.push((name.to_string(), text.lines().count()));
+            text.push_str(&read_input(path)?);
+        }
+
+        Ok(Input{text, starts})
+    }
+
+    /// Describes `error` by the file it occurred in and its line within that file.
+    pub fn describe(&self, mut error: ParseError) -> String {
+        let &(ref name, start) = self.starts.iter().
+            rev().
+            find(|&&(_, start)| start < error.line).
+            unwrap_or(&self.starts[0]);
+        error.line -= start;
+        format!("{}: {}", name, error)
+    }
+}
+
+/// Runs `solver` with command line arguments (excluding the program name).
+pub fn run<S: Solver>(solver: &mut S, args: &[String]) -> Result<String, Error> {
+    let options = parse_options(args).map_err(Error::Usage)?;
+    solver.set_params(&options.params).map_err(Error::Usage)?;
+    let input = Input::read(&options.inputs).map_err(Error::Input)?;
+
+    let start = Instant::now();
+    let parsed = solver.parse(&input.text).map_err(|e| Error::Input(input.describe(e)))?;
+    let answer = ::answer(solver, &parsed, options.part);
+    let elapsed = start.elapsed();
+    solver.write_outputs(&parsed, options.part).map_err(Error::Input)?;
+
+    match options.format {
+        Format::Text => Ok(answer.to_string()),
+        Format::Json => {
+            let report = Json::object(vec![
+                ("day", Json::from(S::DAY)),
+                ("part", Json::from(options.part)),
+                ("answer", answer.value()),
+                ("elapsed_ms", Json::from((elapsed.as_secs_f64() * 1e6).round() / 1e3)),
+                ("extras", Json::object(answer.extras())),
+            ]);
+            Ok(report.to_string())
+        },
+    }
+}
+
+pub fn is_help(args: &[String]) -> bool {
+    args.iter().any(|a| a == "-h" || a == "--help")
+}
+
+pub fn exit_with_usage(message: &str, usage: &str) -> ! {
+    eprintln!("{}\n\n{}", message, usage);
+    process::exit(2);
+}
+
+/// Reports `error` on stderr and exits, adding `usage` for command line mistakes.
+pub fn exit_with_error(error: Error, usage: &str) -> ! {
+    match error {
+        Error::Usage(message) => exit_with_usage(&message, usage),
+        Error::Input(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        },
+    }
+}
+
+/// Entry point for a single day's binary.
+pub fn main<S: Solver>(name: &str, mut solver: S) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let usage = format!("Usage: {} [options] <input>...\n\n\
+        Arguments:\n    <input>...         Puzzle input files, read in order (- reads stdin)\n\n{}", name, OPTIONS);
+    if is_help(&args) {
+        println!("{}", usage);
+        return;
+    }
+
+    match run(&mut solver, &args) {
+        Ok(answer) => println!("{}", answer),
+        Err(error) => exit_with_error(error, &usage),
+    }
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use Solver;
+
+/// Runs every `*.expected` fixture in `dir` and panics listing any answers that differ.
+///
+/// An expected file sits beside the input it checks (`test1.expected` for `test1.dat`)
+/// and holds `key: value` lines: `param` values are passed to a fresh solver in order,
+/// and `part1`/`part2` give the answer as rendered for `--format json`.
+pub fn check<S, F>(new_solver: F, dir: &str)
+    where S: Solver, F: Fn() -> S {
+    let mut expected_files: Vec<_> = fs::read_dir(dir).
+        unwrap_or_else(|e| panic!("{}: {}", dir, e)).
+        filter_map(|entry| entry.ok().map(|e| e.path())).
+        filter(|path| path.extension().is_some_and(|ext| ext == "expected")).
+        collect();
+    expected_files.sort();
+    assert!(!expected_files.is_empty(), "{}: no .expected fixtures", dir);
+
+    let mut failures = vec![];
+    for expected_file in &expected_files {
+        failures.extend(check_fixture(new_solver(), expected_file));
+    }
+
+    if !failures.is_empty() {
+        panic!("{} fixture answer(s) differ:\n{}", failures.len(), failures.join("\n"));
+    }
+}
+
+fn check_fixture<S: Solver>(mut solver: S, expected_file: &Path) -> Vec<String> {
+    let name = expected_file.display();
+    let contents = fs::read_to_string(expected_file).
+        unwrap_or_else(|e| panic!("{}: {}", name, e));
+    let input_file = expected_file.with_extension("dat");
+    let input = fs::read_to_string(&input_file).
+        unwrap_or_else(|e| panic!("{}: {}", input_file.display(), e));
+
+    let mut params = vec![];
+    let mut answers = vec![];
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        let mut fields = line.splitn(2, ':');
+        let key = fields.next().unwrap_or("").trim();
+        let value = fields.next().
+            unwrap_or_else(|| panic!("{}: expected <key>: <value>, found {}", name, line)).
+            trim();
+        match key {
+            "param" => params.push(value.to_string()),
+            "part1" => answers.push((1, value)),
+            "part2" => answers.push((2, value)),
+            _ => panic!("{}: unknown key {}", name, key),
+        }
+    }
+
+    solver.set_params(&params).unwrap_or_else(|e| panic!("{}: {}", name, e));
+
+    let mut failures = vec![];
+    for (part, expected) in answers {
+        let answer = ::solve(&solver, &input, part).
+            unwrap_or_else(|e| panic!("{}: {}", input_file.display(), e));
+        let actual = answer.value().to_string();
+        if actual != expected {
+            failures.push(format!("{} part {}: expected {}, got {}", name, part, expected, actual));
+        }
+    }
+    failures
+}
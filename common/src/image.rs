@@ -0,0 +1,245 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+/// An RGB raster, written out as a binary PPM or an uncompressed PNG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Image {
+        Image { width, height, pixels: vec![fill; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in &self.pixels {
+            out.write_all(pixel)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a PNG whose image data is stored without compression, which keeps the
+    /// encoder to the chunk framing and checksums.
+    pub fn write_png<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend(&(self.width as u32).to_be_bytes());
+        header.extend(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, truecolour, default compression, filtering and no interlace.
+        header.extend(&[8, 2, 0, 0, 0]);
+        write_chunk(out, b"IHDR", &header)?;
+
+        let mut scanlines = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            for pixel in row {
+                scanlines.extend(pixel);
+            }
+        }
+        write_chunk(out, b"IDAT", &zlib_stored(&scanlines))?;
+        write_chunk(out, b"IEND", &[])
+    }
+
+    /// Saves as PNG when `path` ends in `.png`, otherwise as PPM.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut out = BufWriter::new(File::create(path)?);
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("png") => self.write_png(&mut out)?,
+            _ => self.write_ppm(&mut out)?,
+        }
+        out.flush()
+    }
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+fn crc32<'a, I: Iterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Wraps `data` in a zlib stream of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xFFFF;
+
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![data] } else { data.chunks(MAX_BLOCK).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        stream.push(last as u8);
+        stream.extend(&(block.len() as u16).to_le_bytes());
+        stream.extend(&(!(block.len() as u16)).to_le_bytes());
+        stream.extend(*block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    stream.extend(&((b << 16) | a).to_be_bytes());
+    stream
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits a PNG into its chunks, checking the signature and every chunk's CRC.
+    fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut chunks = vec![];
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let crc = u32::from_be_bytes([rest[8 + len], rest[9 + len], rest[10 + len], rest[11 + len]]);
+            assert_eq!(crc, crc32(kind.iter().chain(data)), "{:?}", kind);
+            chunks.push((String::from_utf8(kind.to_vec()).unwrap(), data.to_vec()));
+            rest = &rest[12 + len..];
+        }
+        chunks
+    }
+
+    /// Reads back a zlib stream of stored blocks, returning the data and the block lengths.
+    fn inflate_stored(stream: &[u8]) -> (Vec<u8>, Vec<usize>) {
+        assert_eq!(u16::from_be_bytes([stream[0], stream[1]]) % 31, 0, "zlib header check");
+        let (mut data, mut lengths) = (vec![], vec![]);
+        let mut at = 2;
+        loop {
+            let last = stream[at] == 1;
+            let len = u16::from_le_bytes([stream[at + 1], stream[at + 2]]);
+            assert_eq!(!len, u16::from_le_bytes([stream[at + 3], stream[at + 4]]));
+            data.extend(&stream[at + 5..at + 5 + len as usize]);
+            lengths.push(len as usize);
+            at += 5 + len as usize;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(stream.len(), at + 4, "trailing bytes after the checksum");
+        let adler = u32::from_be_bytes([stream[at], stream[at + 1], stream[at + 2], stream[at + 3]]);
+        assert_eq!(adler, adler32(&data));
+        (data, lengths)
+    }
+
+    /// Adler-32 from its closed form, reducing only at the end.
+    fn adler32(data: &[u8]) -> u32 {
+        let n = data.len() as u64;
+        let a = 1 + data.iter().map(|&d| u64::from(d)).sum::<u64>();
+        let b = n + data.iter().enumerate().map(|(i, &d)| (n - i as u64) * u64::from(d)).sum::<u64>();
+        (((b % 65521) << 16) | (a % 65521)) as u32
+    }
+
+    fn gradient(width: usize, height: usize) -> Image {
+        let mut image = Image::new(width, height, [0, 0, 0]);
+        for y in 0..height {
+            for x in 0..width {
+                image.set(x, y, [x as u8, y as u8, (x * y) as u8]);
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(b"123456789".iter()), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND".iter()), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn ppm_has_a_header_then_raw_pixels() {
+        let mut image = Image::new(2, 2, [1, 2, 3]);
+        image.set(1, 0, [255, 0, 128]);
+        let mut ppm = vec![];
+        image.write_ppm(&mut ppm).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend(&[1, 2, 3, 255, 0, 128, 1, 2, 3, 1, 2, 3]);
+        assert_eq!(ppm, expected);
+    }
+
+    #[test]
+    fn png_chunks_hold_the_pixels() {
+        let image = gradient(3, 2);
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+
+        let chunks = chunks(&png);
+        let kinds: Vec<&str> = chunks.iter().map(|(kind, _)| kind.as_str()).collect();
+        assert_eq!(kinds, vec!["IHDR", "IDAT", "IEND"]);
+        assert_eq!(chunks[0].1, vec![0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        assert!(chunks[2].1.is_empty());
+
+        let (scanlines, _) = inflate_stored(&chunks[1].1);
+        assert_eq!(scanlines, vec![0, 0, 0, 0, 1, 0, 0, 2, 0, 0, 0, 0, 1, 0, 1, 1, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn stored_blocks_split_past_their_limit() {
+        let data: Vec<u8> = (0..150_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let (inflated, lengths) = inflate_stored(&zlib_stored(&data));
+        assert_eq!(lengths, vec![65535, 65535, 18930]);
+        assert_eq!(inflated, data);
+
+        let (inflated, lengths) = inflate_stored(&zlib_stored(&data[..65535]));
+        assert_eq!((inflated.len(), lengths), (65535, vec![65535]));
+        let (inflated, lengths) = inflate_stored(&zlib_stored(&[]));
+        assert_eq!((inflated, lengths), (vec![], vec![0]));
+    }
+
+    #[test]
+    fn large_png_round_trips() {
+        // 120 scanlines of 1 + 3 * 200 bytes need two stored blocks.
+        let image = gradient(200, 120);
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+
+        let chunks = chunks(&png);
+        let (scanlines, lengths) = inflate_stored(&chunks[1].1);
+        assert_eq!(lengths.len(), 2);
+        for (y, row) in scanlines.chunks(1 + 3 * 200).enumerate() {
+            assert_eq!(row[0], 0, "filter type of row {}", y);
+            for (x, pixel) in row[1..].chunks(3).enumerate() {
+                assert_eq!(pixel, image.get(x, y), "pixel {}, {}", x, y);
+            }
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::fmt;
+
+use parse::ParseError;
+
+/// A JSON value, built up by hand for the `--format json` output or read with `Json::parse`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    /// Parses a single JSON value; errors point into `source`.
+    pub fn parse(source: &str) -> Result<Json, ParseError> {
+        let mut reader = Reader { source, pos: 0 };
+        let value = reader.value()?;
+        reader.skip_whitespace();
+        if reader.pos < source.len() {
+            return Err(ParseError::at(source, &source[reader.pos..], "end of JSON"));
+        }
+        Ok(value)
+    }
+
+    /// The value of `key` in an object.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Recursive descent over the text of a JSON value.
+struct Reader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        let rest = self.rest();
+        let token = rest.chars().next().map_or(rest, |c| &rest[..c.len_utf8()]);
+        ParseError::at(self.source, token, expected)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        for (word, value) in [("null", Json::Null), ("true", Json::Bool(true)), ("false", Json::Bool(false))] {
+            if rest.starts_with(word) {
+                self.pos += word.len();
+                return Ok(value);
+            }
+        }
+
+        match rest.chars().next() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("a JSON value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ParseError> {
+        self.eat('{');
+        let mut fields = vec![];
+        if self.eat('}') {
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if !self.rest().starts_with('"') {
+                return Err(self.error("a quoted key"));
+            }
+            let key = self.string()?;
+            if !self.eat(':') {
+                return Err(self.error("':'"));
+            }
+            fields.push((key, self.value()?));
+            if self.eat('}') {
+                return Ok(Json::Object(fields));
+            }
+            if !self.eat(',') {
+                return Err(self.error("',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ParseError> {
+        self.eat('[');
+        let mut values = vec![];
+        if self.eat(']') {
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            if self.eat(']') {
+                return Ok(Json::Array(values));
+            }
+            if !self.eat(',') {
+                return Err(self.error("',' or ']'"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let c = match self.rest().chars().next() {
+                Some(c) => c,
+                None => return Err(self.error("a closing '\"'")),
+            };
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(s);
+                },
+                '\\' => {
+                    self.pos += 1;
+                    s.push(self.escape()?);
+                },
+                c if (c as u32) < 0x20 => return Err(self.error("an escaped control character")),
+                c => {
+                    self.pos += c.len_utf8();
+                    s.push(c);
+                },
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, ParseError> {
+        let c = self.rest().chars().next();
+        let simple = match c {
+            Some('"') => Some('"'),
+            Some('\\') => Some('\\'),
+            Some('/') => Some('/'),
+            Some('b') => Some('\u{8}'),
+            Some('f') => Some('\u{c}'),
+            Some('n') => Some('\n'),
+            Some('r') => Some('\r'),
+            Some('t') => Some('\t'),
+            Some('u') => None,
+            _ => return Err(self.error("an escape sequence")),
+        };
+        self.pos += 1;
+        if let Some(c) = simple {
+            return Ok(c);
+        }
+
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) && self.rest().starts_with("\\u") {
+            self.pos += 2;
+            let low = self.hex4()?;
+            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+        } else {
+            high
+        };
+        Ok(std::char::from_u32(code).unwrap_or('\u{FFFD}'))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let digits = self.rest().get(..4).filter(|d| d.chars().all(|c| c.is_ascii_hexdigit()));
+        match digits {
+            Some(digits) => {
+                self.pos += 4;
+                Ok(u32::from_str_radix(digits, 16).unwrap())
+            },
+            None => Err(self.error("four hex digits")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, ParseError> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c))).unwrap_or(rest.len());
+        let text = &rest[..len];
+        if text.parse::<f64>().is_err() {
+            return Err(ParseError::at(self.source, text, "a number"));
+        }
+        self.pos += len;
+        Ok(Json::Number(text.to_string()))
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::String(s) => write_string(f, s),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+macro_rules! json_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Json {
+            fn from(n: $t) -> Json {
+                Json::Number(n.to_string())
+            }
+        })*
+    }
+}
+
+json_number!(i32, i64, u32, u64, usize);
+
+impl From<f64> for Json {
+    fn from(n: f64) -> Json {
+        if n.is_finite() { Json::Number(n.to_string()) } else { Json::Null }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Json {
+        Json::Bool(b)
+    }
+}
+
+impl<'a> From<&'a str> for Json {
+    fn from(s: &'a str) -> Json {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Json {
+        Json::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
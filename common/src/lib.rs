@@ -0,0 +1,97 @@
+use std::fmt;
+
+pub mod cli;
+pub mod fixtures;
+pub mod image;
+pub mod json;
+pub mod parse;
+pub mod rng;
+
+pub use json::Json;
+pub use parse::ParseError;
+
+/// A puzzle answer: printed as prose, or as the bare value plus extras for `--format json`.
+pub trait Answer: fmt::Display {
+    fn value(&self) -> Json;
+
+    /// Day-specific details of how the answer was reached.
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        vec![]
+    }
+}
+
+macro_rules! plain_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn value(&self) -> Json {
+                Json::from(self.clone())
+            }
+        })*
+    }
+}
+
+plain_answer!(i32, u32, u64, usize, String);
+
+/// A day's puzzle: the input is parsed once and either part is answered from it.
+pub trait Solver {
+    const DAY: u32;
+
+    type Input;
+    type Part1: Answer + 'static;
+    type Part2: Answer + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Self::Part1;
+    fn part2(&self, input: &Self::Input) -> Self::Part2;
+
+    /// Applies the day-specific `--param` values given on the command line.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        match params.first() {
+            Some(param) => Err(format!("Unexpected parameter: {}", param)),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes any files the `--param` values asked for alongside `part`. Only the command
+    /// line runner calls this, so benchmarks and fixtures leave the disk alone.
+    fn write_outputs(&self, _input: &Self::Input, _part: u32) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A solver with its types erased, so the runner can choose one by name.
+pub trait Puzzle {
+    /// Runs with command line arguments, as `cli::run` does.
+    fn run(&mut self, args: &[String]) -> Result<String, cli::Error>;
+    fn solve(&self, input: &str, part: u32) -> Result<Box<dyn Answer>, ParseError>;
+}
+
+impl<S: Solver> Puzzle for S {
+    fn run(&mut self, args: &[String]) -> Result<String, cli::Error> {
+        cli::run(self, args)
+    }
+
+    fn solve(&self, input: &str, part: u32) -> Result<Box<dyn Answer>, ParseError> {
+        solve(self, input, part)
+    }
+}
+
+/// Solves one part of the puzzle.
+pub fn solve<S: Solver>(solver: &S, input: &str, part: u32) -> Result<Box<dyn Answer>, ParseError> {
+    let input = solver.parse(input)?;
+    Ok(answer(solver, &input, part))
+}
+
+/// Answers one part of the puzzle from parsed input.
+pub fn answer<S: Solver>(solver: &S, input: &S::Input, part: u32) -> Box<dyn Answer> {
+    if part == 1 {
+        Box::new(solver.part1(input))
+    } else {
+        Box::new(solver.part2(input))
+    }
+}
+
+/// Parses a single day-specific parameter, naming it in the error.
+pub fn parse_param<T: std::str::FromStr>(param: &str, name: &str) -> Result<T, String> {
+    param.parse().map_err(|_| format!("Invalid {}: {}", name, param))
+}
@@ -0,0 +1,84 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed piece of puzzle input: where it is, what it says and what was expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error for `token`, which must be a slice of `source`; line and column are
+    /// found from its position. Pass `&source[source.len()..]` for a premature end.
+    pub fn at(source: &str, token: &str, expected: &str) -> ParseError {
+        let start = source.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).saturating_sub(start).min(source.len());
+        let before = &source[..offset];
+
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        ParseError{line, column, text: token.to_string(), expected: expected.to_string()}
+    }
+
+    /// Repositions an error found in `token`, a slice of `source`, relative to `source`.
+    pub fn within(mut self, source: &str, token: &str) -> ParseError {
+        let outer = ParseError::at(source, token, "");
+        if self.line == 1 {
+            self.column += outer.column - 1;
+        }
+        self.line += outer.line - 1;
+        self
+    }
+
+    /// Moves the error down by `lines`, for sources that are one line of a larger input.
+    pub fn offset_lines(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, found ", self.line, self.column, self.expected)?;
+        if self.text.is_empty() {
+            write!(f, "end of input")
+        } else {
+            write!(f, "\"{}\"", self.text)
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses `token`, a slice of `source`, as a number.
+pub fn parse_number<T: FromStr>(source: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    let token = token.trim();
+    token.parse().map_err(|_| ParseError::at(source, token, expected))
+}
+
+/// Parses `token`, a slice of `source`, as two numbers separated by `separator`.
+pub fn parse_pair<T: FromStr>(source: &str, token: &str, separator: char, expected: &str) -> Result<(T, T), ParseError> {
+    let mut parts = token.splitn(2, separator);
+    match (parts.next(), parts.next()) {
+        (Some(a), Some(b)) => {
+            Ok((parse_number(source, a, expected)?, parse_number(source, b, expected)?))
+        },
+        _ => Err(ParseError::at(source, token, expected)),
+    }
+}
+
+/// Parses every non-blank line of `input` with `parse_line`, numbering any error by its line.
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&str) -> Result<T, ParseError> {
+    input.lines().
+        enumerate().
+        filter(|(_, line)| !line.trim().is_empty()).
+        map(|(i, line)| parse_line(line).map_err(|e| e.offset_lines(i))).
+        collect()
+}
@@ -0,0 +1,16 @@
+/// A seeded linear congruential generator, so randomised tests see the same data on every run.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng{state: seed}
+    }
+
+    /// The next value, somewhere in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.state >> 33) % bound
+    }
+}
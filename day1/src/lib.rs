@@ -0,0 +1,283 @@
+extern crate common;
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::collections::HashMap;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::{parse_lines, parse_number};
+
+pub fn accumulated_frequency(start_freq: i32, adjustments: &[i32]) -> i32 {
+    let mut sum = start_freq;
+    for adj in adjustments {
+        sum += adj;
+    }
+    sum
+}
+
+/// Where a frequency was first reached a second time: `pass` full passes over the
+/// adjustments have completed and `index` is the adjustment about to be applied.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Repeat {
+    pub frequency: i32,
+    pub pass: usize,
+    pub index: usize,
+}
+
+/// Finds the first repeated frequency without simulating the passes, or `None` when the
+/// frequencies drift forever without meeting.
+///
+/// Every pass shifts the frequencies of the first pass by the total drift, so a later
+/// frequency can only meet an earlier one that shares its remainder modulo the drift
+/// and lies ahead of it in the direction of the drift.
+pub fn first_repeated_cumulative_freq(start_freq: i32, adjustments: &[i32]) -> Option<Repeat> {
+    let mut prefix = Vec::with_capacity(adjustments.len());
+    let mut first_seen = HashMap::new();
+    let mut current_freq = start_freq;
+
+    for (index, adj) in adjustments.iter().enumerate() {
+        if first_seen.contains_key(&current_freq) {
+            return Some(Repeat { frequency: current_freq, pass: 0, index });
+        }
+        first_seen.insert(current_freq, index);
+        prefix.push(current_freq);
+        current_freq += adj;
+    }
+
+    if adjustments.is_empty() {
+        return None;
+    }
+    let drift = i64::from(current_freq) - i64::from(start_freq);
+    if drift == 0 {
+        return Some(Repeat { frequency: start_freq, pass: 1, index: 0 });
+    }
+
+    // Order the first pass by remainder, then by position along the drift, so that each
+    // frequency's next meeting is with its neighbour in the same remainder class.
+    let modulus = drift.abs();
+    let mut order: Vec<usize> = (0..prefix.len()).collect();
+    order.sort_by_key(|&i| {
+        let f = i64::from(prefix[i]);
+        (f.rem_euclid(modulus), f * drift.signum())
+    });
+
+    let mut best: Option<(i64, Repeat)> = None;
+    for pair in order.windows(2) {
+        let (i, j) = (pair[0], pair[1]);
+        let (from, to) = (i64::from(prefix[i]), i64::from(prefix[j]));
+        if from.rem_euclid(modulus) != to.rem_euclid(modulus) {
+            continue;
+        }
+
+        let passes = (to - from) / drift;
+        let time = passes * prefix.len() as i64 + i as i64;
+        if best.is_none_or(|(best_time, _)| time < best_time) {
+            let repeat = Repeat { frequency: prefix[j], pass: passes as usize, index: i };
+            best = Some((time, repeat));
+        }
+    }
+
+    best.map(|(_, repeat)| repeat)
+}
+
+/// The frequencies reached while applying the adjustments, through the first repeat.
+pub struct FrequencyTrace {
+    pub start_freq: i32,
+    /// Frequencies seen on the first pass, before each adjustment is applied.
+    pub frequencies: Vec<i32>,
+    pub final_frequency: i32,
+    pub repeat: Option<Repeat>,
+    /// Lowest and highest frequencies reached up to the first repeat, or over the
+    /// first pass when nothing repeats.
+    pub min: i64,
+    pub max: i64,
+}
+
+impl FrequencyTrace {
+    pub fn new(start_freq: i32, adjustments: &[i32]) -> FrequencyTrace {
+        let final_frequency = accumulated_frequency(start_freq, adjustments);
+        let repeat = first_repeated_cumulative_freq(start_freq, adjustments);
+
+        let mut frequencies = Vec::with_capacity(adjustments.len());
+        let mut current_freq = start_freq;
+        for adj in adjustments {
+            frequencies.push(current_freq);
+            current_freq += adj;
+        }
+
+        let range = |fs: &[i32]| {
+            let lo = fs.iter().min().map_or(i64::from(start_freq), |&f| i64::from(f));
+            let hi = fs.iter().max().map_or(i64::from(start_freq), |&f| i64::from(f));
+            (lo, hi)
+        };
+
+        // Later passes repeat the first shifted by the drift, so only the first and last
+        // full passes and the partial pass ending at the repeat can hold the extremes.
+        let drift = i64::from(final_frequency) - i64::from(start_freq);
+        let (min, max) = match repeat {
+            None => {
+                let (lo, hi) = range(&frequencies);
+                (lo.min(i64::from(final_frequency)), hi.max(i64::from(final_frequency)))
+            },
+            Some(repeat) => {
+                let shift = repeat.pass as i64 * drift;
+                let (lo, hi) = range(&frequencies[..=repeat.index]);
+                let (mut min, mut max) = (lo + shift, hi + shift);
+                if repeat.pass > 0 {
+                    let (lo, hi) = range(&frequencies);
+                    let last_shift = shift - drift;
+                    min = min.min(lo).min(lo + last_shift);
+                    max = max.max(hi).max(hi + last_shift);
+                }
+                (min, max)
+            },
+        };
+
+        FrequencyTrace { start_freq, frequencies, final_frequency, repeat, min, max }
+    }
+
+    /// The index of the adjustment that produced the repeated frequency.
+    pub fn trigger(&self) -> Option<usize> {
+        let len = self.frequencies.len();
+        self.repeat.map(|repeat| (repeat.index + len - 1) % len)
+    }
+
+    /// Every frequency reached, as `(pass, index, frequency)`, through the first repeat
+    /// or for a single pass when nothing repeats; nothing without any adjustments.
+    pub fn steps(&self) -> impl Iterator<Item = (usize, usize, i64)> + '_ {
+        let drift = i64::from(self.final_frequency) - i64::from(self.start_freq);
+        let (last_pass, last_index) = match self.repeat {
+            Some(repeat) => (repeat.pass, repeat.index),
+            None => (0, self.frequencies.len().saturating_sub(1)),
+        };
+
+        let passes = if self.frequencies.is_empty() { 0 } else { last_pass + 1 };
+        (0..passes).flat_map(move |pass| {
+            let end = if pass == last_pass { last_index + 1 } else { self.frequencies.len() };
+            self.frequencies[..end].iter().enumerate().
+                map(move |(index, &f)| (pass, index, i64::from(f) + pass as i64 * drift))
+        })
+    }
+
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "step,pass,index,frequency")?;
+        for (step, (pass, index, frequency)) in self.steps().enumerate() {
+            writeln!(out, "{},{},{},{}", step, pass, index, frequency)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct FinalFrequency(pub i32);
+
+impl fmt::Display for FinalFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Final frequency is: {}", self.0)
+    }
+}
+
+impl Answer for FinalFrequency {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+pub struct DuplicateFrequency(pub FrequencyTrace);
+
+impl fmt::Display for DuplicateFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.repeat {
+            Some(repeat) => write!(f, "First duplicate frequency is: {}", repeat.frequency),
+            None => write!(f, "No frequency is ever repeated"),
+        }
+    }
+}
+
+impl Answer for DuplicateFrequency {
+    fn value(&self) -> Json {
+        Json::from(self.0.repeat.map(|repeat| repeat.frequency))
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let trace = &self.0;
+        let mut extras = vec![("min", Json::from(trace.min)), ("max", Json::from(trace.max))];
+        if let Some(repeat) = trace.repeat {
+            extras.push(("pass", Json::from(repeat.pass)));
+            extras.push(("index", Json::from(repeat.index)));
+            extras.push(("trigger", Json::from(trace.trigger())));
+        }
+        extras
+    }
+}
+
+#[derive(Default)]
+pub struct Day1 {
+    /// Where to write the cumulative frequency trace as CSV, if anywhere.
+    pub trace_csv: Option<String>,
+}
+
+impl Solver for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<i32>;
+    type Part1 = FinalFrequency;
+    type Part2 = DuplicateFrequency;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        parse_lines(input, |l| parse_number(l, l.trim(), "a signed frequency change, e.g. +7"))
+    }
+
+    fn part1(&self, adjustments: &Vec<i32>) -> FinalFrequency {
+        FinalFrequency(accumulated_frequency(0, adjustments))
+    }
+
+    fn part2(&self, adjustments: &Vec<i32>) -> DuplicateFrequency {
+        DuplicateFrequency(FrequencyTrace::new(0, adjustments))
+    }
+
+    /// Writes the part 2 frequency trace, when asked for.
+    fn write_outputs(&self, adjustments: &Vec<i32>, part: u32) -> Result<(), String> {
+        match self.trace_csv {
+            Some(ref path) if part == 2 => {
+                let trace = FrequencyTrace::new(0, adjustments);
+                File::create(path).and_then(|file| {
+                    let mut out = BufWriter::new(file);
+                    trace.write_csv(&mut out)?;
+                    out.flush()
+                }).map_err(|e| format!("{}: {}", path, e))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Takes a file to write the part 2 frequency trace to as CSV.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        self.trace_csv = params.first().cloned();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_without_adjustments_has_no_steps() {
+        let trace = FrequencyTrace::new(0, &[]);
+        assert_eq!(trace.steps().count(), 0);
+
+        let mut csv = vec![];
+        trace.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "step,pass,index,frequency\n");
+    }
+
+    #[test]
+    fn trace_runs_through_the_first_repeat() {
+        let trace = FrequencyTrace::new(0, &[1, -2, 3, 1]);
+        let steps: Vec<_> = trace.steps().collect();
+        assert_eq!(steps.first(), Some(&(0, 0, 0)));
+        assert_eq!(steps.last(), Some(&(1, 2, 2)));
+        assert_eq!(steps.len(), 7);
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day1;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day1::Day1::default, env!("CARGO_MANIFEST_DIR"));
+}
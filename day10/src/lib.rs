@@ -0,0 +1,225 @@
+extern crate common;
+extern crate grid;
+
+use std::fmt;
+use std::collections::HashMap;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::parse_lines;
+use grid::{Coord, Point};
+
+/// Parses a point written as `<x, y>`, reporting errors against the whole line.
+fn parse_point(line: &str, token: &str) -> Result<Point, ParseError> {
+    let coords = token.trim();
+    if !coords.starts_with('<') || !coords.ends_with('>') {
+        return Err(ParseError::at(line, coords, "<x, y>"));
+    }
+    let inner = &coords[1..coords.len() - 1];
+    inner.parse().map_err(|e: ParseError| e.within(line, inner))
+}
+
+#[derive(Copy,Clone,Hash,Eq,PartialEq,PartialOrd,Ord)]
+pub struct Particle {
+    pub position: Point,
+    pub velocity: Point,
+}
+
+impl Particle {
+    pub fn forward(& mut self) {
+        self.position += self.velocity;
+    }
+
+    pub fn backward(& mut self) {
+        self.position -= self.velocity;
+    }
+}
+
+impl fmt::Display for Particle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "position=({})  velocity=({})", self.position, self.velocity)
+    }
+}
+
+impl std::str::FromStr for Particle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grammar = "position=<x, y> velocity=<dx, dy>";
+        let (position, velocity) = match s.find("velocity=") {
+            Some(i) if s.trim_start().starts_with("position=") => (&s[..i], &s[i..]),
+            _ => return Err(ParseError::at(s, s.trim(), grammar)),
+        };
+
+        let position = position.trim_start().trim_start_matches("position=");
+        let velocity = velocity.trim_start_matches("velocity=");
+
+        Ok(Particle {
+            position: parse_point(s, position)?,
+            velocity: parse_point(s, velocity)?,
+        })
+    }
+}
+
+pub struct ParticleGrid {
+    particles: HashMap<Coord,Vec<Particle>>,
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+}
+
+impl ParticleGrid {
+    pub fn new(particles: Vec<Particle>) -> ParticleGrid {
+        let min_x = particles.iter().map(|p| p.position.x).min().unwrap();
+        let max_x = particles.iter().map(|p| p.position.x).max().unwrap();
+
+        let min_y = particles.iter().map(|p| p.position.y).min().unwrap();
+        let max_y = particles.iter().map(|p| p.position.y).max().unwrap();
+
+        let mut grid: HashMap<Coord, Vec<Particle>> = HashMap::new();
+        for particle in particles.iter() {
+            let col = (particle.position.x - min_x) as usize;
+            let row = (particle.position.y - min_y) as usize;
+            let position = grid.entry(Coord::new(row, col)).or_insert(vec![]);
+            position.push(*particle);
+        }
+
+        ParticleGrid { particles: grid, min_x, max_x, min_y, max_y }
+    }
+
+    pub fn forward(&mut self) {
+        for ps in self.particles.values_mut() {
+            for particle in ps.iter_mut() {
+                particle.forward();
+            }
+        }
+        let particles: Vec<Particle> = self.particles.values().
+            flat_map(|ps| ps.iter()).copied().
+            collect();
+
+        *self = ParticleGrid::new(particles);
+    }
+
+    pub fn backward(&mut self) {
+        for ps in self.particles.values_mut() {
+            for particle in ps.iter_mut() {
+                particle.backward();
+            }
+        }
+        let particles: Vec<Particle> = self.particles.values().
+            flat_map(|ps| ps.iter()).copied().
+            collect();
+
+        *self = ParticleGrid::new(particles);
+    }
+
+    pub fn width(&self) -> u32 {
+        (self.max_x - self.min_x) as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        (self.max_y - self.min_y) as u32
+    }
+}
+
+impl fmt::Display for ParticleGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cols = (self.max_x - self.min_x + 1) as usize;
+        let rows = (self.max_y - self.min_y + 1) as usize;
+
+        let last_col = cols - 1;
+
+        for row in 0..rows {
+            for col in 0..cols {
+                if self.particles.contains_key(&Coord::new(row, col)) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+
+                if col == last_col {
+                    writeln!(f)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Answer for ParticleGrid {
+    fn value(&self) -> Json {
+        Json::from(self.to_string())
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        vec![("width", Json::from(self.width())), ("height", Json::from(self.height()))]
+    }
+}
+
+/// Steps the particles until the bounding box stops shrinking, returning the elapsed
+/// seconds and the grid at its smallest.
+pub fn align(particles: Vec<Particle>) -> (u32, ParticleGrid) {
+    let mut grid = ParticleGrid::new(particles);
+
+    let mut last_width = grid.width();
+    let mut last_height = grid.height();
+    let mut i = 1u32;
+    loop {
+        grid.forward();
+
+        let current_width = grid.width();
+        let current_height = grid.height();
+        if current_height > last_height || current_width > last_width {
+            break;
+        }
+        last_height = current_height;
+        last_width = current_width;
+        i += 1;
+    }
+    i -= 1;
+    grid.backward();
+    (i, grid)
+}
+
+pub struct Seconds(pub u32);
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "After {} second(s)", self.0)
+    }
+}
+
+impl Answer for Seconds {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<Particle>;
+    type Part1 = ParticleGrid;
+    type Part2 = Seconds;
+
+    fn parse(&self, input: &str) -> Result<Vec<Particle>, ParseError> {
+        let particles = parse_lines(input, str::parse::<Particle>)?;
+        if particles.is_empty() {
+            return Err(ParseError::at(input, &input[input.len()..], "position=<x, y> velocity=<dx, dy>"));
+        }
+        Ok(particles)
+    }
+
+    fn part1(&self, particles: &Vec<Particle>) -> ParticleGrid {
+        align(particles.clone()).1
+    }
+
+    fn part2(&self, particles: &Vec<Particle>) -> Seconds {
+        Seconds(align(particles.clone()).0)
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day10;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day10::Day10, env!("CARGO_MANIFEST_DIR"));
+}
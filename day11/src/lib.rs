@@ -0,0 +1,162 @@
+extern crate common;
+
+use std::fmt;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::parse_number;
+
+pub type PowerGrid = [[i32; 300]; 300];
+
+
+pub fn power_level(x: u32, y: u32, serial_number: u32) -> i32 {
+    let rack_id = x + 10;
+    let pl = ((rack_id * y) + serial_number) * rack_id;
+    ((pl / 100) % 10) as i32 - 5
+}
+
+pub fn max_power_square(grid: &PowerGrid, size: usize) -> (usize, usize, i32) {
+    let adj = size - 1;
+    let base_max_power_level = -300*((size*size) as i32);
+
+    let mut grid_rows = grid.len();
+    let mut grid_cols = grid[0].len();
+
+    grid_cols -= adj;
+    let mut col_reduced_grid = vec![vec![0i32; grid_cols]; grid_rows];
+
+    for row in 0..grid_rows {
+        for col in 0..grid_cols {
+            for dcol in 0usize..size {
+                col_reduced_grid[row][col] += grid[row][col+dcol];
+            }
+        }
+    }
+
+    grid_rows -= adj;
+    let mut reduced_grid = vec![vec![0i32; grid_cols]; grid_rows];
+
+    for col in 0..grid_cols {
+        for row in 0..grid_rows {
+            for drow in 0usize..size {
+                reduced_grid[row][col] += col_reduced_grid[row+drow][col];
+            }
+        }
+    }
+
+    let mut max_power_level = base_max_power_level;
+    let mut left_x = 1usize;
+    let mut top_y = 1usize;
+
+    for (row, values) in reduced_grid.iter().enumerate() {
+        for (col, &value) in values.iter().enumerate() {
+            if max_power_level < value {
+                max_power_level = value;
+                left_x = col + 1;
+                top_y = row + 1;
+            }
+        }
+    }
+
+    (top_y, left_x, max_power_level)
+}
+
+
+pub fn power_grid(serial_number: u32) -> PowerGrid {
+    let mut grid = [[0i32; 300]; 300];
+
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            let x = row + 1;
+            let y = col + 1;
+            *cell = power_level(x as u32, y as u32, serial_number);
+        }
+    }
+
+    grid
+}
+
+pub struct PowerSquare {
+    pub top_y: usize,
+    pub left_x: usize,
+    pub size: usize,
+    pub power_level: i32,
+}
+
+impl fmt::Display for PowerSquare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {}) produces {}", self.top_y, self.left_x, self.size, self.power_level)
+    }
+}
+
+impl Answer for PowerSquare {
+    /// The square's identifier `x,y,size`.
+    fn value(&self) -> Json {
+        Json::from(format!("{},{},{}", self.top_y, self.left_x, self.size))
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        vec![
+            ("x", Json::from(self.top_y)),
+            ("y", Json::from(self.left_x)),
+            ("size", Json::from(self.size)),
+            ("power_level", Json::from(self.power_level)),
+        ]
+    }
+}
+
+/// A square of known size, answered by its top-left fuel cell `x,y` alone.
+pub struct TopLeftCell(pub PowerSquare);
+
+impl fmt::Display for TopLeftCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Answer for TopLeftCell {
+    fn value(&self) -> Json {
+        Json::from(format!("{},{}", self.0.top_y, self.0.left_x))
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        self.0.extras()
+    }
+}
+
+pub fn best_power_square(grid: &PowerGrid, size: usize) -> PowerSquare {
+    let (top_y, left_x, power_level) = max_power_square(grid, size);
+    PowerSquare{top_y, left_x, size, power_level}
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u32 = 11;
+
+    // Boxed to keep the 300x300 grid off the stack
+    type Input = Box<PowerGrid>;
+    type Part1 = TopLeftCell;
+    type Part2 = PowerSquare;
+
+    fn parse(&self, input: &str) -> Result<Box<PowerGrid>, ParseError> {
+        let serial_number = parse_number(input, input.trim(), "a grid serial number")?;
+        Ok(Box::new(power_grid(serial_number)))
+    }
+
+    fn part1(&self, grid: &Box<PowerGrid>) -> TopLeftCell {
+        TopLeftCell(best_power_square(grid, 3))
+    }
+
+    fn part2(&self, grid: &Box<PowerGrid>) -> PowerSquare {
+        let mut best = best_power_square(grid, 1);
+
+        for size in 2usize..300 {
+            let square = best_power_square(grid, size);
+            if best.power_level < square.power_level {
+                best = square;
+            }
+        }
+
+        best
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day11;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day11::Day11, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,196 @@
+extern crate common;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common::{Solver, ParseError};
+
+/// Checks that `token`, a slice of `line`, is a row of `#` and `.` pots.
+fn parse_pots<'a>(line: &str, token: &'a str, expected: &str) -> Result<&'a str, ParseError> {
+    match token.find(|c| c != '#' && c != '.') {
+        Some(i) => Err(ParseError::at(line, &token[i..], expected)),
+        None => Ok(token),
+    }
+}
+
+fn parse_initial_state(line: &str) -> Result<&str, ParseError> {
+    let grammar = "initial state: <pots>";
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 3 || tokens[0] != "initial" || tokens[1] != "state:" {
+        return Err(ParseError::at(line, line.trim(), grammar));
+    }
+    parse_pots(line, tokens[2], grammar)
+}
+
+fn parse_rule(line: &str) -> Result<(&str, &str), ParseError> {
+    let grammar = "<5 pots> => <pot>";
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 3 || tokens[1] != "=>" || tokens[0].len() != 5 || tokens[2].len() != 1 {
+        return Err(ParseError::at(line, line.trim(), grammar));
+    }
+    Ok((parse_pots(line, tokens[0], grammar)?, parse_pots(line, tokens[2], grammar)?))
+}
+
+#[derive(Clone)]
+pub struct PlantSystem {
+    min_value: i32,
+    max_value: i32,
+    values: HashSet<i32>,
+    rules: HashMap<String, String>,
+}
+
+impl PlantSystem {
+    pub fn new(input: &str) -> Result<PlantSystem, ParseError> {
+        let mut lines = input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+
+        let initial_state = match lines.next() {
+            Some((_, line)) => line,
+            None => return Err(ParseError::at(input, &input[input.len()..], "initial state: <pots>")),
+        };
+        let state = parse_initial_state(initial_state)?;
+
+        let mut max_value = 0;
+        let mut values = HashSet::new();
+        for (i, c) in state.chars().enumerate() {
+            if c == '#' {
+                values.insert(i as i32);
+                max_value = i as i32;
+            }
+        }
+
+        let mut rules = HashMap::new();
+        for (i, line) in lines {
+            let (pattern, result) = parse_rule(line).map_err(|e| e.offset_lines(i))?;
+            rules.insert(pattern.to_string(), result.to_string());
+        }
+
+        Ok(PlantSystem{min_value: 0i32, max_value, values, rules})
+    }
+
+    pub fn total_value(&self) -> i32 {
+//        let mut vals = self.values.iter().collect::<Vec<&i32>>();
+//        vals.sort();
+//
+//        let vstring = vals.iter().
+//            map(|i| i.to_string()).
+//            collect::<Vec<String>>().join(" -> ");
+//
+//        println!("{}", vstring);
+
+        self.values.iter().sum()
+    }
+
+    fn neighbors(&self, idx: i32) -> String {
+        let lower_bound = idx - 2;
+        let upper_bound = idx + 2;
+
+        (lower_bound..=upper_bound).map(|i|
+            if self.values.contains(&i) {'#'} else {'.'}
+        ).collect::<String>()
+    }
+
+    pub fn advance(&mut self) {
+        let mut values = HashSet::new();
+        let lower_bound = self.min_value - 3;
+        let upper_bound = self.max_value + 3;
+
+        let mut min_value = upper_bound;
+        let mut max_value = lower_bound;
+
+        for idx in lower_bound..=upper_bound {
+            let n = self.neighbors(idx);
+            let rule_value = match self.rules.get(&n) {
+                None => ".",
+                Some(v) => v,
+            };
+
+            if rule_value == "#" {
+                values.insert(idx);
+
+                if idx < min_value {
+                    min_value = idx;
+                }
+
+                if idx > max_value {
+                    max_value = idx;
+                }
+            }
+        }
+        self.values = values;
+        self.min_value = min_value;
+        self.max_value = max_value;
+    }
+}
+
+impl std::fmt::Display for PlantSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let plants = (self.min_value..=self.max_value).map(|i|
+            if self.values.contains(&i) {'#'} else {'.'}
+        ).collect::<String>();
+        write!(f, "{}", plants)
+    }
+}
+
+/// Sum of the pot numbers holding plants after `n` generations, extrapolating
+/// once the pattern settles into a steady drift.
+pub fn total_after(plant_system: &mut PlantSystem, n: usize) -> u64 {
+    let mut last_str = plant_system.to_string();
+    let mut last_total_value = plant_system.total_value() as u64;
+
+    for i in 0..n {
+        plant_system.advance();
+        let current_str = plant_system.to_string();
+        let current_total_value = plant_system.total_value() as u64;
+
+        if current_str == last_str && current_total_value != last_total_value {
+            let delta = current_total_value - last_total_value;
+            last_total_value = (n - i - 1) as u64 * delta + current_total_value;
+            break;
+        } else {
+            last_str = current_str;
+            last_total_value = current_total_value;
+        }
+    }
+
+    last_total_value
+}
+
+#[derive(Default)]
+pub struct Day12 {
+    pub generations: Option<usize>,
+}
+
+impl Day12 {
+    fn total_after(&self, plant_system: &PlantSystem, default_generations: usize) -> u64 {
+        let mut plant_system = plant_system.clone();
+        total_after(&mut plant_system, self.generations.unwrap_or(default_generations))
+    }
+}
+
+impl Solver for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = PlantSystem;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(&self, input: &str) -> Result<PlantSystem, ParseError> {
+        PlantSystem::new(input)
+    }
+
+    fn part1(&self, plant_system: &PlantSystem) -> u64 {
+        self.total_after(plant_system, 20)
+    }
+
+    fn part2(&self, plant_system: &PlantSystem) -> u64 {
+        self.total_after(plant_system, 50_000_000_000)
+    }
+
+    /// Overrides the number of generations to simulate.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        if let Some(param) = params.first() {
+            self.generations = Some(common::parse_param(param, "number of generations")?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day12;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day12::Day12::default, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,222 @@
+extern crate common;
+extern crate grid;
+
+use std::fmt;
+use std::collections::HashSet;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use common::{Solver, ParseError, Answer, Json};
+use grid::{Coord, Direction, Grid};
+
+#[derive(Copy, Clone)]
+enum Turn {
+    Left, Straight, Right
+}
+
+#[derive(Copy, Clone)]
+struct Cart {
+    direction: Direction,
+    next_turn: Turn,
+}
+
+impl Cart {
+    fn new(direction: Direction) -> Cart {
+        Cart{direction, next_turn: Turn::Left}
+    }
+}
+
+#[derive(Clone)]
+pub struct TrackSystem {
+    tracks: Grid<char>,
+    carts: HashMap<Coord,Cart>,
+    /// In the order they happened, so the first crash of a tick is the first in reading order.
+    crashes: Vec<Coord>,
+    errors: HashSet<Coord>,
+}
+
+impl TrackSystem {
+    pub fn new(s: &str) -> Result<TrackSystem, ParseError> {
+        let mut tracks = Grid::parse(s, "a track piece or cart", |c| match c {
+            ' ' | '-' | '|' | '/' | '\\' | '+' | '^' | 'v' | '<' | '>' => Some(c),
+            _ => None,
+        })?;
+
+        let mut carts = HashMap::new();
+        for (coord, &c) in tracks.iter() {
+            if let Some(direction) = Direction::from_char(c) {
+                carts.insert(coord, Cart::new(direction));
+            }
+        }
+
+        // A lone cart never crashes, so neither part could finish
+        if carts.len() < 2 {
+            return Err(ParseError::at(s, &s[s.len()..], "at least two carts"));
+        }
+
+        // Carts start on straight track running their way
+        for (&coord, cart) in carts.iter() {
+            tracks[coord] = match cart.direction {
+                Direction::North | Direction::South => '|',
+                Direction::West | Direction::East => '-',
+            };
+        }
+
+        Ok(TrackSystem{tracks, carts, crashes: Vec::new(), errors: HashSet::new()})
+    }
+
+    pub fn forward(&mut self) {
+        let mut positions: Vec<Coord> = self.carts.keys().cloned().collect();
+        positions.sort_unstable();
+
+        for old_position in positions {
+            let mut cart = match self.carts.remove(&old_position) {
+                Some(cart) => cart,
+                None => continue,
+            };
+
+            let new_position = match self.tracks.step(old_position, cart.direction) {
+                Some(position) if self.tracks[position] != ' ' => position,
+                _ => {
+                    self.errors.insert(old_position);
+                    continue;
+                },
+            };
+
+            cart.direction = match self.tracks[new_position] {
+                '+' => match cart.next_turn {
+                    Turn::Left => {
+                        cart.next_turn = Turn::Straight;
+                        cart.direction.turn_left()
+                    },
+                    Turn::Straight => {
+                        cart.next_turn = Turn::Right;
+                        cart.direction
+                    },
+                    Turn::Right => {
+                        cart.next_turn = Turn::Left;
+                        cart.direction.turn_right()
+                    },
+                },
+                '/' => match cart.direction {
+                    Direction::North | Direction::South => cart.direction.turn_right(),
+                    Direction::West | Direction::East => cart.direction.turn_left(),
+                },
+                '\\' => match cart.direction {
+                    Direction::North | Direction::South => cart.direction.turn_left(),
+                    Direction::West | Direction::East => cart.direction.turn_right(),
+                },
+                _ => cart.direction,
+            };
+
+            if let Entry::Vacant(e) = self.carts.entry(new_position) {
+                e.insert(cart);
+            } else {
+                self.carts.remove(&new_position);
+                self.crashes.push(new_position);
+            }
+        }
+    }
+}
+
+impl TrackSystem {
+    pub fn first_crash(&mut self) -> Option<Coord> {
+        while self.errors.is_empty() && self.crashes.is_empty() {
+            self.forward();
+        }
+
+        self.crashes.first().cloned()
+    }
+
+    pub fn last_cart(&mut self) -> Option<Coord> {
+        while self.errors.is_empty() && self.carts.len() > 1 {
+            self.forward();
+        }
+
+        if self.carts.len() == 1 {
+            self.carts.keys().next().cloned()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for TrackSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = self.tracks.render(|coord, &c| {
+            if self.crashes.contains(&coord) {
+                'X'
+            } else {
+                self.carts.get(&coord).map_or(c, |cart| cart.direction.to_char())
+            }
+        });
+
+        for (r, line) in map.lines().enumerate() {
+            writeln!(f, "{:03}{}", r, line)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub enum Survivor {
+    Cart(Coord),
+    EvenCarts,
+}
+
+impl fmt::Display for Survivor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Survivor::Cart(coord) => write!(f, "{}", coord),
+            Survivor::EvenCarts => write!(f, "Even number of carts!"),
+        }
+    }
+}
+
+impl Answer for Survivor {
+    fn value(&self) -> Json {
+        match self {
+            Survivor::Cart(coord) => coord.value(),
+            Survivor::EvenCarts => Json::Null,
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = TrackSystem;
+    type Part1 = Coord;
+    type Part2 = Survivor;
+
+    fn parse(&self, input: &str) -> Result<TrackSystem, ParseError> {
+        TrackSystem::new(input)
+    }
+
+    fn part1(&self, track_system: &TrackSystem) -> Coord {
+        track_system.clone().first_crash().expect("Carts left the track!")
+    }
+
+    fn part2(&self, track_system: &TrackSystem) -> Survivor {
+        match track_system.clone().last_cart() {
+            Some(coord) => Survivor::Cart(coord),
+            None => Survivor::EvenCarts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_fewer_than_two_carts() {
+        for tracks in &["/-\\\n| |\n\\-/\n", "/>\\\n| |\n\\-/\n"] {
+            let e = TrackSystem::new(tracks).err().expect(tracks);
+            assert_eq!((e.line, e.expected.as_str()), (4, "at least two carts"));
+        }
+        assert!(TrackSystem::new("/>\\\n| |\n\\</\n").is_ok());
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day13;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day13::Day13, env!("CARGO_MANIFEST_DIR"));
+}
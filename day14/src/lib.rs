@@ -0,0 +1,121 @@
+extern crate common;
+
+use common::{Solver, ParseError};
+use common::parse::parse_number;
+
+
+pub fn digits_for(n: usize) -> Vec<usize> {
+    let mut m = n;
+    let mut digits = Vec::new();
+
+    if m == 0 {
+        digits.push(0);
+    } else {
+        while m > 0 {
+            digits.push(m % 10);
+            m /= 10;
+        }
+
+        digits.reverse();
+    }
+    digits
+}
+
+pub fn create_new_recipes(recipes: &mut Vec<usize>, index_one: &mut usize, index_two: &mut usize) {
+    let score_one = recipes[*index_one];
+    let score_two = recipes[*index_two];
+
+    let n: usize = score_one + score_two;
+    let mut digits = digits_for(n);
+
+    recipes.append(&mut digits);
+
+    let m = recipes.len();
+
+    *index_one = (*index_one + score_one + 1usize) % m;
+    *index_two = (*index_two + score_two + 1usize) % m;
+}
+
+/// Scores of the ten recipes made after the first `recipe_count`.
+pub fn scores_after(recipe_count: usize) -> String {
+    let mut recipes: Vec<usize> = vec![3, 7];
+
+    let mut index_one = 0;
+    let mut index_two = 1;
+
+    let full_recipe_count = recipe_count + 10;
+    for _ in 0..full_recipe_count {
+        create_new_recipes(&mut recipes, &mut index_one, &mut index_two);
+    }
+
+    let last_recipes = &recipes[recipe_count..full_recipe_count];
+    last_recipes.iter().map(|r| r.to_string()).collect()
+}
+
+/// Number of recipes made before the score sequence `digits` first appears.
+pub fn recipes_before(digits: &[usize]) -> usize {
+    let mut recipes: Vec<usize> = vec![3, 7];
+
+    let mut index_one = 0;
+    let mut index_two = 1;
+
+    let n = digits.len();
+    let mut start = 0;
+
+    loop {
+        create_new_recipes(&mut recipes, &mut index_one, &mut index_two);
+
+        let m = recipes.len();
+        if m >= n {
+            let mut matches = true;
+
+            for j in 0..2 {
+                if m >= n + j {
+                    start = m - n - j;
+                    let finish = start + n;
+                    matches = recipes[start..finish] == digits[..];
+
+                    if matches {
+                        break;
+                    }
+                } else {
+                    matches = false;
+                    break;
+                }
+            }
+
+            if matches {
+                break;
+            }
+        }
+    }
+    start
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = String;
+    type Part1 = String;
+    type Part2 = usize;
+
+    /// Keeps the input as its digit string, which part 2 searches for.
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        let digits = input.trim();
+        parse_number::<usize>(input, digits, "a recipe count of decimal digits")?;
+        Ok(digits.to_string())
+    }
+
+    fn part1(&self, input: &String) -> String {
+        scores_after(input.parse::<usize>().unwrap())
+    }
+
+    fn part2(&self, input: &String) -> usize {
+        let digits: Vec<usize> = input.chars().
+            map(|ch| ch.to_digit(10).unwrap() as usize).
+            collect();
+        recipes_before(&digits)
+    }
+}
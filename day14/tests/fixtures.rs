@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day14;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day14::Day14, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,391 @@
+extern crate common;
+extern crate grid;
+
+use std::fmt;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use common::{Solver, ParseError, Answer, Json};
+use grid::{Coord, Direction, Grid};
+
+use self::UnitKind::*;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum UnitKind {
+    Elf, Goblin
+}
+
+#[derive(Copy, Clone)]
+struct Unit {
+    kind: UnitKind,
+    attack_power: u32,
+    hit_points: i32,
+}
+
+impl Unit {
+    fn new(kind: UnitKind, attack_power: u32) -> Unit {
+        Unit{kind, attack_power, hit_points: 200}
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sym = match self.kind {
+            Elf => 'E',
+            Goblin => 'G',
+        };
+        write!(f, "{}({})", sym, self.hit_points)
+    }
+}
+
+#[derive(Clone)]
+pub struct CombatBoard {
+    cavern: Grid<char>,
+    elves: HashSet<Coord>,
+    goblins: HashSet<Coord>,
+    units: HashMap<Coord,Unit>,
+    allow_dead_elves: bool,
+    starting_elf_count: usize,
+    rounds: u32,
+}
+
+impl CombatBoard {
+    pub fn new(s: &str, elf_attack_power: u32, allow_dead_elves: bool) -> Result<CombatBoard, ParseError> {
+        let goblin_attack_power = 3;
+        let mut elves = HashSet::new();
+        let mut goblins = HashSet::new();
+        let mut units = HashMap::new();
+
+        let mut cavern = Grid::parse(s, "a wall, open cavern, elf or goblin", |c| match c {
+            '#' | '.' | ' ' | 'E' | 'G' => Some(c),
+            _ => None,
+        })?;
+
+        for (coord, &c) in cavern.iter() {
+            match c {
+                'E' => {
+                    elves.insert(coord);
+                    units.insert(coord, Unit::new(Elf, elf_attack_power));
+                },
+                'G' => {
+                    goblins.insert(coord);
+                    units.insert(coord, Unit::new(Goblin, goblin_attack_power));
+                },
+                _ => {},
+            }
+        }
+        for &coord in units.keys() {
+            cavern[coord] = '.';
+        }
+
+        let starting_elf_count = elves.len();
+
+        Ok(CombatBoard{cavern, elves, goblins, units, allow_dead_elves, starting_elf_count, rounds: 0})
+    }
+
+    pub fn an_elf_has_died(&self) -> bool {
+        self.elves.len() < self.starting_elf_count
+    }
+
+    pub fn is_finished(&self) -> bool {
+        if self.allow_dead_elves {
+            self.elves.is_empty() || self.goblins.is_empty()
+        } else {
+            self.elves.len() < self.starting_elf_count || self.goblins.is_empty()
+        }
+    }
+
+    fn sorted_unit_positions(&self) -> Vec<Coord> {
+        let mut positions: Vec<Coord> = self.units.keys().cloned().collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    fn step(&self, direction: Direction, coord: Coord) -> Option<Coord> {
+        self.cavern.step(coord, direction).filter(|&c| self.cavern[c] != '#')
+    }
+
+    fn find_best_move(&self, position: Coord, kind: UnitKind) -> Option<Coord> {
+        let (friends, enemies) = match kind {
+            Elf => (&self.elves, &self.goblins),
+            Goblin => (&self.goblins, &self.elves),
+        };
+
+        let mut visited = HashSet::new();
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(position);
+
+        let mut dest_coord = None;
+        let mut seeking_dest = true;
+
+        while seeking_dest {
+            let coord = queue.pop_front().unwrap();
+            visited.insert(coord);
+
+            for &dir in Direction::ALL.iter() {
+                if let Some(new_coord) = self.step(dir, coord) {
+                    if !visited.contains(&new_coord) &&
+                        !friends.contains(&new_coord) {
+                        if enemies.contains(&new_coord) {
+                            let mut current_coord = coord;
+                            loop {
+                                match previous.get(&current_coord) {
+                                    Some(&prev) => {
+                                        if prev == position {
+                                            break;
+                                        }
+                                        current_coord = prev;
+                                    },
+                                    None => {
+                                        if current_coord == position {
+                                            return None
+                                        } else {
+                                            panic!("{} lacks a predecessor!", current_coord);
+                                        }
+                                    }
+                                }
+                                let &prev = previous.get(&current_coord).unwrap();
+                                if prev == position {
+                                    break;
+                                }
+                                current_coord = prev;
+                            }
+
+                            dest_coord = Some(current_coord);
+                            seeking_dest = false;
+                            break;
+                        } else if let Entry::Vacant(entry) = previous.entry(new_coord) {
+                            entry.insert(coord);
+                            queue.push_back(new_coord);
+                        }
+                    }
+                }
+            }
+            seeking_dest = seeking_dest && !queue.is_empty();
+        }
+
+        dest_coord
+    }
+
+    fn find_best_target(&self, coord: Coord, kind: UnitKind) -> Option<Coord> {
+        let enemies = match kind {
+            Elf => &self.goblins,
+            Goblin => &self.elves,
+        };
+        let mut min_hit_points = 0;
+        let mut best_target = None;
+
+        for &dir in Direction::ALL.iter() {
+
+            if let Some(new_coord) = self.step(dir, coord) {
+                if enemies.contains(&new_coord) {
+                    let enemy = self.units.get(&new_coord).unwrap();
+                    if best_target.is_none() || min_hit_points > enemy.hit_points {
+                        best_target = Some(new_coord);
+                        min_hit_points = enemy.hit_points;
+                    }
+                }
+            }
+        }
+        best_target
+    }
+
+    fn make_move(&mut self, start: Coord, finish: Coord) {
+        let unit = self.units.remove(&start).unwrap();
+        match unit.kind {
+            Elf => {
+                self.elves.remove(&start);
+                self.elves.insert(finish);
+            },
+            Goblin => {
+                self.goblins.remove(&start);
+                self.goblins.insert(finish);
+            }
+        }
+        self.units.insert(finish, unit);
+    }
+
+    fn attack_target(&mut self, position: Coord, target: Coord) {
+        let attack_power = self.units.get(&position).unwrap().attack_power;
+        let (kind, defeated) = {
+            let defender = self.units.get_mut(&target).unwrap();
+            defender.hit_points -= attack_power as i32;
+            (defender.kind, defender.hit_points <= 0)
+        };
+
+        if defeated {
+            match kind {
+                Elf => self.elves.remove(&target),
+                Goblin => self.goblins.remove(&target),
+            };
+            self.units.remove(&target);
+        }
+    }
+
+    pub fn execute_round(&mut self) {
+        let positions = self.sorted_unit_positions();
+        let mut positions_left = positions.len();
+
+        for &position in positions.iter() {
+            positions_left -= 1;
+            let (kind, living) = {
+                match self.units.get(&position) {
+                    Some(unit) => (unit.kind, true),
+                    None => (Elf, false),
+                }
+            };
+
+            if living {
+                let new_position =
+                    match self.find_best_move(position, kind) {
+                        Some(coord) => {
+                            self.make_move(position, coord);
+                            coord
+                        }
+                        None => position,
+                    };
+
+                if let Some(target) = self.find_best_target(new_position, kind) {
+                    self.attack_target(new_position, target);
+                    if self.is_finished() && positions_left > 0 {
+                        return;
+                    }
+                }
+            }
+        }
+        self.rounds += 1;
+        //println!("After {} rounds:\n{}", self.rounds, self);
+    }
+
+    /// A copy of this board, before any rounds, with a new elf attack power.
+    pub fn with_elf_attack_power(&self, attack_power: u32, allow_dead_elves: bool) -> CombatBoard {
+        let mut board = self.clone();
+        for unit in board.units.values_mut() {
+            if unit.kind == Elf {
+                unit.attack_power = attack_power;
+            }
+        }
+        board.allow_dead_elves = allow_dead_elves;
+        board
+    }
+
+    pub fn fight(&mut self) {
+        while !self.is_finished() {
+            self.execute_round();
+        }
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// Hit points left across every unit still standing.
+    pub fn total_hit_points(&self) -> u32 {
+        self.units.values().
+            map(|u| u.hit_points as u32).sum()
+    }
+
+    pub fn outcome(&self) -> u32 {
+        self.total_hit_points() * self.rounds
+    }
+}
+
+impl fmt::Display for CombatBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = self.cavern.render(|coord, &c| match self.units.get(&coord) {
+            Some(unit) if unit.kind == Elf => 'E',
+            Some(_) => 'G',
+            None => c,
+        });
+
+        for (r, line) in map.lines().enumerate() {
+            let tail: Vec<String> = self.units.iter().
+                filter(|(coord, _)| coord.row == r).
+                collect::<BTreeMap<_, _>>().
+                values().
+                map(|unit| unit.to_string()).
+                collect();
+
+            if !tail.is_empty() {
+                writeln!(f, "{}  {}", line.trim_end(), tail.join(", "))?;
+            } else {
+                writeln!(f, "{}", line.trim_end())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+pub struct Battle {
+    pub initial: CombatBoard,
+    pub elf_attack_power: Option<u32>,
+    pub board: CombatBoard,
+}
+
+impl fmt::Display for Battle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.initial)?;
+        if let Some(attack_power) = self.elf_attack_power {
+            writeln!(f, "Elf Attack Power: {}", attack_power)?;
+        }
+        writeln!(f, "{}", self.board)?;
+        write!(f, "Outcome: {}", self.board.outcome())
+    }
+}
+
+impl Answer for Battle {
+    fn value(&self) -> Json {
+        Json::from(self.board.outcome())
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let mut extras = vec![
+            ("rounds", Json::from(self.board.rounds())),
+            ("hit_points", Json::from(self.board.total_hit_points())),
+        ];
+        if let Some(attack_power) = self.elf_attack_power {
+            extras.push(("elf_attack_power", Json::from(attack_power)));
+        }
+        extras
+    }
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = CombatBoard;
+    type Part1 = Battle;
+    type Part2 = Battle;
+
+    fn parse(&self, input: &str) -> Result<CombatBoard, ParseError> {
+        CombatBoard::new(input, 3, true)
+    }
+
+    fn part1(&self, initial: &CombatBoard) -> Battle {
+        let mut board = initial.clone();
+        board.fight();
+        Battle{initial: initial.clone(), elf_attack_power: None, board}
+    }
+
+    /// Finds the lowest elf attack power that wins without losing a single elf.
+    fn part2(&self, initial: &CombatBoard) -> Battle {
+        let mut attack_power = 4;
+        loop {
+            let mut board = initial.with_elf_attack_power(attack_power, false);
+            board.fight();
+            if board.an_elf_has_died() {
+                attack_power += 1;
+            } else {
+                return Battle{initial: initial.clone(), elf_attack_power: Some(attack_power), board};
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day15;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day15::Day15, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,493 @@
+extern crate common;
+
+use std::fmt;
+use std::slice::Iter;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::parse_number;
+
+use self::Instruction::*;
+
+pub type RegisterSet = [u32; 4];
+
+/// Parses four numbers from `tokens`, slices of `a`, reporting a shortfall or surplus as `expected`.
+fn parse_four<'a, I>(a: &str, tokens: I, expected: &str) -> Result<[u32; 4], ParseError>
+    where I: Iterator<Item = &'a str> {
+    let mut values = [0; 4];
+    let mut count = 0;
+
+    for token in tokens {
+        if count == 4 {
+            return Err(ParseError::at(a, token, expected));
+        }
+        values[count] = parse_number(a, token, expected)?;
+        count += 1;
+    }
+
+    if count < 4 {
+        return Err(ParseError::at(a, &a[a.len()..], expected));
+    }
+    Ok(values)
+}
+
+pub fn parse_register_set(a: &str) -> Result<RegisterSet, ParseError> {
+    let grammar = "[<r0>, <r1>, <r2>, <r3>]";
+    let registers = a.trim();
+    if !registers.starts_with('[') || !registers.ends_with(']') {
+        return Err(ParseError::at(a, registers, grammar));
+    }
+
+    parse_four(a, registers[1..registers.len() - 1].split(','), grammar)
+}
+
+pub type InstructionCall = [u32; 4];
+
+pub fn parse_instruction_call(a: &str) -> Result<InstructionCall, ParseError> {
+    parse_four(a, a.split_whitespace(), "<opcode> <a> <b> <c>")
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CpuSample {
+    pub before: RegisterSet,
+    pub instruction_call: InstructionCall,
+    pub after: RegisterSet,
+}
+
+impl CpuSample {
+    pub fn get_possible_instructions(&self) -> HashSet<Instruction> {
+        let mut possible_instructions = HashSet::new();
+        let a = self.instruction_call[1];
+        let b = self.instruction_call[2];
+        let c = self.instruction_call[3] as usize;
+        for instruction in Instruction::iter() {
+            let mut before = self.before;
+
+            instruction.execute(&mut before, a, b, c);
+            if before == self.after {
+                possible_instructions.insert(*instruction);
+            }
+        }
+        possible_instructions
+    }
+}
+
+impl fmt::Display for CpuSample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Before: {:?}", self.before)?;
+        writeln!(f, "{:?}", self.instruction_call)?;
+        writeln!(f, "After: {:?}", self.after)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori, Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Addr => "addr", Addi => "addi", Mulr => "mulr", Muli => "muli",
+            Banr => "banr", Bani => "bani", Borr => "borr", Bori => "bori",
+            Setr => "setr", Seti => "seti", Gtir => "gtir", Gtri => "gtri", Gtrr => "gtrr",
+            Eqir => "eqir", Eqri => "eqri", Eqrr => "eqrr",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Instruction {
+    pub fn iter() -> Iter<'static, Instruction> {
+        static INSTRUCTIONS: [Instruction;  16] = [
+            Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori,
+            Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr
+        ];
+        INSTRUCTIONS.iter()
+    }
+
+    pub fn execute(&self, register_set: &mut RegisterSet, a: u32, b: u32, c: usize) {
+        match self {
+            Addr => register_set[c] = register_set[a as usize] + register_set[b as usize],
+            Addi => register_set[c] = register_set[a as usize] + b,
+            Mulr => register_set[c] = register_set[a as usize] * register_set[b as usize],
+            Muli => register_set[c] = register_set[a as usize] * b,
+            Banr => register_set[c] = register_set[a as usize] & register_set[b as usize],
+            Bani => register_set[c] = register_set[a as usize] & b,
+            Borr => register_set[c] = register_set[a as usize] | register_set[b as usize],
+            Bori => register_set[c] = register_set[a as usize] | b,
+            Setr => register_set[c] = register_set[a as usize],
+            Seti => register_set[c] = a,
+            Gtir => register_set[c] = if a > register_set[b as usize] { 1 } else { 0 },
+            Gtri => register_set[c] = if register_set[a as usize] > b { 1 } else { 0 },
+            Gtrr => register_set[c] = if register_set[a as usize] > register_set[b as usize] { 1 } else { 0 },
+            Eqir => register_set[c] = if a == register_set[b as usize] { 1 } else { 0 },
+            Eqri => register_set[c] = if register_set[a as usize] == b { 1 } else { 0 },
+            Eqrr => register_set[c] = if register_set[a as usize] == register_set[b as usize] { 1 } else { 0 },
+        }
+    }
+}
+
+pub struct HopcroftKarp<'a> {
+    ubound: usize,
+    vbound: usize,
+    adj: &'a HashMap<usize,Vec<usize>>,
+    infinity: u32,
+    pair_u: Vec<usize>,
+    pair_v: Vec<usize>,
+    dist: Vec<u32>,
+}
+
+impl<'a> fmt::Display for HopcroftKarp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "ubound: {}", self.ubound)?;
+        writeln!(f, "vbound: {}", self.vbound)?;
+        writeln!(f, "infinity: {}", self.infinity)?;
+        write!(f, "     u: ")?;
+        for u in 0..(self.ubound) {
+            write!(f, "{:02} ", u)?;
+        }
+        write!(f, "\npair_u: ")?;
+        for val in &self.pair_u {
+            write!(f, "{:02} ", val)?;
+        }
+        writeln!(f)?;
+        write!(f, "pair_v: ")?;
+        for val in &self.pair_v {
+            write!(f, "{:02} ", val)?;
+        }
+        writeln!(f)?;
+        write!(f, "  dist: ")?;
+        for val in &self.dist {
+            write!(f, "{:02} ", val)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "-----")
+    }
+}
+
+impl<'a> HopcroftKarp<'a> {
+    pub fn perform(ubound: usize, vbound: usize, adj: &'a HashMap<usize,Vec<usize>>) -> HashMap<usize,usize> {
+        let infinity = (ubound + vbound + 1) as u32;
+        let pair_u: Vec<usize> = vec![];
+        let pair_v: Vec<usize> = vec![];
+        let dist: Vec<u32> = vec![infinity; ubound+1];
+
+        let mut state = HopcroftKarp{ubound, vbound, adj, infinity, pair_u, pair_v, dist};
+
+        state.hopcroft_karp();
+
+        let mut matching = HashMap::new();
+        for u in 0..ubound {
+            matching.insert(u, state.pair_u[u]);
+        }
+
+        matching
+    }
+
+    fn hopcroft_karp(&mut self) {
+        for _ in 0..(self.ubound) {
+            self.pair_u.push(self.ubound);
+        }
+
+        for _ in 0..(self.vbound) {
+            self.pair_v.push(self.vbound);
+        }
+
+        while self.bfs() {
+            for u in 0..(self.ubound) {
+                if self.pair_u[u] == self.ubound {
+                    self.dfs(u);
+                }
+            }
+        }
+    }
+
+    fn bfs(&mut self) -> bool {
+        let mut q = VecDeque::new();
+        for u in 0..(self.ubound) {
+            if self.pair_u[u] == self.ubound {
+                self.dist[u] = 0;
+                q.push_back(u);
+            } else {
+                self.dist[u] = self.infinity;
+            }
+        }
+
+        self.dist[self.ubound] = self.infinity;
+
+        while !q.is_empty() {
+            let u: usize = q.pop_front().unwrap();
+            if self.dist[u] < self.dist[self.ubound] {
+                if let Some(adj) = self.adj.get(&u) { for v in adj.iter() {
+                    let v_u = self.pair_v[*v];
+                    if self.dist[v_u] == self.infinity {
+                        self.dist[v_u] = self.dist[u] + 1;
+                        q.push_back(v_u);
+                    }
+                } }
+            }
+        }
+        self.dist[self.ubound] != self.infinity
+    }
+
+    fn dfs(&mut self, u: usize) -> bool {
+        if u != self.ubound {
+            if let Some(adj) = self.adj.get(&u) { for v in adj.iter() {
+                let v_u = self.pair_v[*v];
+                if self.dist[v_u] == self.dist[u] + 1
+                    && self.dfs(v_u) {
+                        self.pair_v[*v] = u;
+                        self.pair_u[u] = *v;
+                        return true;
+                    }
+            } }
+            self.dist[u] = self.infinity;
+            return false;
+        }
+        true
+    }
+}
+
+/// Parses a sample line such as `Before: [3, 2, 1, 1]` found at `line_no`.
+fn parse_labeled_registers((line_no, line): (usize, &str), label: &str) -> Result<RegisterSet, ParseError> {
+    let registers = line.trim_start();
+    if !registers.starts_with(label) {
+        let expected = format!("{} [<r0>, <r1>, <r2>, <r3>]", label);
+        return Err(ParseError::at(line, registers, &expected).offset_lines(line_no));
+    }
+
+    let registers = &registers[label.len()..];
+    parse_register_set(registers).
+        map_err(|e| e.within(line, registers).offset_lines(line_no))
+}
+
+/// Checks the opcode of the instruction call on `line`, found at `line_no`, names an instruction.
+fn check_opcode((line_no, line): (usize, &str), call: &InstructionCall) -> Result<(), ParseError> {
+    if call[0] as usize >= Instruction::iter().len() {
+        let opcode = line.split_whitespace().next().unwrap_or(line);
+        let expected = format!("an opcode below {}", Instruction::iter().len());
+        return Err(ParseError::at(line, opcode, &expected).offset_lines(line_no));
+    }
+    Ok(())
+}
+
+/// The samples and test program from the manual.
+pub struct Manual {
+    pub samples: Vec<CpuSample>,
+    pub program: Vec<InstructionCall>,
+}
+
+impl Manual {
+    pub fn parse(input: &str) -> Result<Manual, ParseError> {
+        let mut samples = vec![];
+        let mut program = vec![];
+        let mut program_lines = vec![];
+        let mut lines = vec![];
+        let mut i = 0;
+
+        for (line_no, line) in input.lines().enumerate() {
+            if i < 3 {
+                if line.trim().is_empty() {
+                    i = 4;
+                }
+                lines.push((line_no, line));
+                i += 1;
+            } else if i == 3 {
+                i = 0;
+                let before = parse_labeled_registers(lines[0], "Before:")?;
+                let instruction_call = parse_instruction_call(lines[1].1).
+                    map_err(|e| e.offset_lines(lines[1].0))?;
+                check_opcode(lines[1], &instruction_call)?;
+                let after = parse_labeled_registers(lines[2], "After:")?;
+                samples.push(CpuSample{before, instruction_call, after});
+                lines.clear();
+            } else if !line.trim().is_empty() {
+                let instruction_call = parse_instruction_call(line).
+                    map_err(|e| e.offset_lines(line_no))?;
+                check_opcode((line_no, line), &instruction_call)?;
+                program.push(instruction_call);
+                program_lines.push((line_no, line));
+            }
+        }
+
+        // The program may only use opcodes the samples decode to an instruction
+        let manual = Manual{samples, program};
+        let opcode_map = manual.opcode_map();
+        for (call, &(line_no, line)) in manual.program.iter().zip(&program_lines) {
+            if !opcode_map.contains_key(&(call[0] as usize)) {
+                let opcode = line.split_whitespace().next().unwrap_or(line);
+                let sampled = manual.samples.iter().any(|sample| sample.instruction_call[0] == call[0]);
+                let expected = if sampled {
+                    "an opcode whose samples agree on an instruction"
+                } else {
+                    "an opcode seen in the samples"
+                };
+                return Err(ParseError::at(line, opcode, expected).offset_lines(line_no));
+            }
+        }
+
+        Ok(manual)
+    }
+
+    /// Matches each opcode number to the index of its instruction in `Instruction::iter()`,
+    /// leaving out opcodes the samples cannot decode.
+    pub fn opcode_map(&self) -> HashMap<usize,usize> {
+        let mut opcode_table = HashMap::new();
+        for sample in &self.samples {
+            let opcode = sample.instruction_call[0];
+            let new_values = sample.get_possible_instructions();
+
+            if opcode_table.contains_key(&opcode) {
+                let current_values: HashSet<Instruction> = opcode_table.remove(&opcode).unwrap();
+                let updated_values: HashSet<Instruction> = current_values.intersection(&new_values).
+                    copied().collect();
+                opcode_table.insert(opcode, updated_values);
+            } else {
+                opcode_table.insert(opcode, new_values);
+            }
+        }
+
+        let mut opcode_adj = HashMap::new();
+        let insts: Vec<Instruction> = Instruction::iter().
+            copied().collect();
+        let ubound = insts.len();
+        let vbound = insts.len();
+
+        for u in 0..ubound {
+            let mut adjs = vec![];
+            if let Some(u_insts) = opcode_table.get(&(u as u32)) {
+                for (v, inst) in insts.iter().enumerate() {
+                    if u_insts.contains(inst) {
+                        adjs.push(v);
+                    }
+                }
+            }
+            opcode_adj.insert(u, adjs);
+        }
+
+        // Unmatched opcodes are paired with `vbound`
+        HopcroftKarp::perform(ubound, vbound, &opcode_adj).into_iter().
+            filter(|&(_, v)| v < vbound).
+            collect()
+    }
+}
+
+pub struct ProgramRun {
+    pub opcode_map: HashMap<usize,usize>,
+    pub registers: RegisterSet,
+}
+
+impl fmt::Display for ProgramRun {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for opcode in 0..Instruction::iter().len() {
+            match self.opcode_map.get(&opcode) {
+                Some(v) => writeln!(f, "{} => {}", opcode, *v)?,
+                None => writeln!(f, "No mapping for {}", opcode)?,
+            }
+        }
+        write!(f, "Register 0 contains: {}", self.registers[0])
+    }
+}
+
+impl Answer for ProgramRun {
+    fn value(&self) -> Json {
+        Json::from(self.registers[0])
+    }
+
+    /// The opcode map, naming the instruction each opcode number decodes to.
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let mut opcodes: Vec<(&usize, &usize)> = self.opcode_map.iter().collect();
+        opcodes.sort();
+        let opcode_map = opcodes.into_iter().
+            map(|(opcode, &index)| {
+                let name = Instruction::iter().nth(index).map(|i| i.to_string());
+                (opcode.to_string(), Json::from(name))
+            }).
+            collect();
+        vec![("opcode_map", Json::Object(opcode_map))]
+    }
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = Manual;
+    type Part1 = usize;
+    type Part2 = ProgramRun;
+
+    fn parse(&self, input: &str) -> Result<Manual, ParseError> {
+        Manual::parse(input)
+    }
+
+    fn part1(&self, manual: &Manual) -> usize {
+        manual.samples.iter().
+            filter(|sample| sample.get_possible_instructions().len() >= 3).
+            count()
+    }
+
+    fn part2(&self, manual: &Manual) -> ProgramRun {
+        let opcode_map = manual.opcode_map();
+        let insts: Vec<Instruction> = Instruction::iter().copied().collect();
+        let mut registers: RegisterSet = [0; 4];
+
+        for program_instruction in &manual.program {
+            let opcode = program_instruction[0] as usize;
+            let inst = insts[opcode_map[&opcode]];
+
+            let a = program_instruction[1];
+            let b = program_instruction[2];
+            let c = program_instruction[3];
+
+            inst.execute(&mut registers, a, b, c as usize);
+        }
+
+        ProgramRun{opcode_map, registers}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n\n";
+
+    fn error(input: &str) -> (usize, usize, String) {
+        let e = Manual::parse(input).err().expect(input);
+        (e.line, e.column, e.expected)
+    }
+
+    #[test]
+    fn decodes_only_sampled_opcodes() {
+        let manual = Manual::parse(&format!("{}\n\n9 1 2 0\n", SAMPLE)).unwrap();
+        let opcode_map = manual.opcode_map();
+        assert_eq!(opcode_map.keys().collect::<Vec<_>>(), vec![&9]);
+        let decoded = Instruction::iter().nth(opcode_map[&9]).unwrap();
+        assert!(manual.samples[0].get_possible_instructions().contains(decoded));
+        assert_eq!(Day16.part2(&manual).opcode_map, opcode_map);
+    }
+
+    #[test]
+    fn rejects_opcodes_out_of_range() {
+        let sample = SAMPLE.replace("9 2 1 2", "16 2 1 2");
+        assert_eq!(error(&sample), (2, 1, "an opcode below 16".to_string()));
+        assert_eq!(error(&format!("{}\n\n17 1 2 0\n", SAMPLE)), (7, 1, "an opcode below 16".to_string()));
+    }
+
+    #[test]
+    fn rejects_unsampled_opcodes() {
+        assert_eq!(error(&format!("{}\n\n9 1 2 0\n3 1 2 0\n", SAMPLE)), (8, 1, "an opcode seen in the samples".to_string()));
+    }
+
+    #[test]
+    fn rejects_samples_that_disagree() {
+        let sample = "Before: [0, 0, 0, 0]\n0 0 0 0\nAfter:  [0, 0, 0, 0]\n\n";
+        let contradiction = "Before: [0, 0, 0, 0]\n0 0 0 0\nAfter:  [0, 0, 0, 5]\n\n";
+        let input = format!("{}{}\n\n0 0 0 0\n", sample, contradiction);
+        assert_eq!(error(&input), (11, 1, "an opcode whose samples agree on an instruction".to_string()));
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day16;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day16::Day16, env!("CARGO_MANIFEST_DIR"));
+}
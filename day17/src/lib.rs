@@ -0,0 +1,387 @@
+extern crate common;
+extern crate grid;
+
+use std::fmt;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::{parse_lines, parse_number};
+use grid::{Coord, Direction};
+
+use grid::Direction::{East, South, West};
+use self::GroundState::*;
+use self::Process::*;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum GroundState {
+    Clay, Sand, Spring, WetSand, Water, Mark
+}
+
+impl fmt::Display for GroundState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sym = match self {
+            Clay => '#', Sand => '.', Spring => '+',
+            WetSand => '|', Water => '~', Mark => 'M',
+        };
+        write!(f, "{}", sym)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+enum Process {
+    Flowing, SpreadingLeft, SpreadingRight
+}
+
+impl fmt::Display for Process {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let word = match self {
+            Flowing => "Flowing",
+            SpreadingLeft => "SpreadingLeft",
+            SpreadingRight => "SpreadingRight",
+        };
+        write!(f, "{}", word)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+struct FlowState {
+    process: Process,
+    pos: Coord,
+    direction: Direction,
+    edges_index: Option<usize>,
+}
+
+impl fmt::Display for FlowState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}, {}", self.process, self.pos, self.direction)
+    }
+}
+
+impl FlowState {
+    fn new(process: Process, pos: Coord, direction: Direction, edges_index: Option<usize>) -> FlowState {
+        FlowState{process, pos, direction, edges_index}
+    }
+}
+
+const VEIN_GRAMMAR: &str = "x=<col>, y=<row>..<row> or y=<row>, x=<col>..<col>";
+
+/// Parses a vein of clay into its fixed axis, the position on that axis and the range along the other.
+fn parse_vein(line: &str) -> Result<(&'static str, usize, (usize, usize)), ParseError> {
+    let tokens: Vec<&str> = line.trim().split(", ").collect();
+    if tokens.len() != 2 {
+        return Err(ParseError::at(line, line.trim(), VEIN_GRAMMAR));
+    }
+
+    let (fixed_axis, range_axis) = if tokens[0].starts_with("x=") {
+        ("x=", "y=")
+    } else if tokens[0].starts_with("y=") {
+        ("y=", "x=")
+    } else {
+        return Err(ParseError::at(line, tokens[0], VEIN_GRAMMAR));
+    };
+    if !tokens[1].starts_with(range_axis) {
+        return Err(ParseError::at(line, tokens[1], VEIN_GRAMMAR));
+    }
+
+    let fixed = parse_number(line, &tokens[0][2..], VEIN_GRAMMAR)?;
+    let range = &tokens[1][2..];
+    let (start, end) = match range.find("..") {
+        Some(i) => (&range[..i], &range[i + 2..]),
+        None => return Err(ParseError::at(line, range, "a range <start>..<end>")),
+    };
+    let start = parse_number(line, start, "a range <start>..<end>")?;
+    let end = parse_number(line, end, "a range <start>..<end>")?;
+    if start > end {
+        return Err(ParseError::at(line, range, "a range whose start is no greater than its end"));
+    }
+
+    Ok((fixed_axis, fixed, (start, end)))
+}
+
+#[derive(Clone)]
+pub struct GroundScan {
+    patches: HashMap<Coord,GroundState>,
+    spring: Coord,
+    min_scan_row: usize,
+    max_scan_row: usize,
+    visited: u32,
+}
+
+impl GroundScan {
+    pub fn new(s: &str, spring: Coord) -> Result<GroundScan, ParseError> {
+        let mut patches = HashMap::new();
+
+        for (axis, fixed, (start, end)) in parse_lines(s, parse_vein)? {
+            for varying in start..=end {
+                let coord = if axis == "x=" {
+                    Coord{row: varying, col: fixed}
+                } else {
+                    Coord{row: fixed, col: varying}
+                };
+                patches.insert(coord, Clay);
+            }
+        }
+
+        if patches.is_empty() {
+            return Err(ParseError::at(s, &s[s.len()..], VEIN_GRAMMAR));
+        }
+
+        let min_scan_row = patches.keys().
+            map(|p| p.row).min().unwrap();
+        let max_scan_row = patches.keys().
+            map(|p| p.row).max().unwrap();
+        let visited = 0;
+
+        patches.insert(spring, Spring);
+        Ok(GroundScan{patches, spring, min_scan_row, max_scan_row, visited})
+    }
+
+    fn get_patch(&self, pos: Coord) -> GroundState {
+        *(self.patches.get(&pos).unwrap_or(&Sand))
+    }
+
+    /// The next position in `direction`; water falling past the deepest clay is lost.
+    fn step(&self, pos: Coord, direction: Direction) -> Option<Coord> {
+        if direction == South && pos.row >= self.max_scan_row {
+            return None;
+        }
+        pos.step(direction)
+    }
+
+    fn mark_wet_sand(&mut self, pos: Coord) {
+        if self.get_patch(pos) == Sand {
+            if pos.row >= self.min_scan_row {
+                self.visited += 1;
+            }
+            self.patches.insert(pos, WetSand);
+        }
+    }
+
+    fn print_diagnostic(&mut self, state: &FlowState, patch: GroundState) {
+        println!("{} -> {}", state, patch);
+        let temp = self.get_patch(state.pos);
+        self.patches.insert(state.pos, Mark);
+        println!("{}", self);
+        self.patches.insert(state.pos, temp);
+    }
+
+    pub fn trapped_spots_count(&self) -> usize {
+        let mut trapped_spots = 0;
+        for ground_state in self.patches.values() {
+            if *ground_state == Water {
+                trapped_spots += 1;
+            }
+        }
+        trapped_spots
+    }
+
+    fn flow(&mut self, state: &FlowState, patch: GroundState, new_pos: Coord,
+            nodes: &mut VecDeque<FlowState>, edges: &mut Vec<(Coord, Option<Coord>, Option<Coord>)>) {
+        match state.direction {
+            South => match patch {
+                Sand | WetSand  => {
+                    self.mark_wet_sand(new_pos);
+                    nodes.push_back(FlowState::new(Flowing, new_pos, South, None));
+                },
+                Clay | Water => {
+                    edges.push((state.pos, None, None));
+                    let edge_index = edges.len() - 1;
+                    nodes.push_back(FlowState::new(SpreadingLeft, state.pos, West, Some(edge_index)));
+                    nodes.push_back(FlowState::new(SpreadingRight, state.pos, East, Some(edge_index)));
+                },
+                Spring => {
+                    self.print_diagnostic(state, patch);
+                    panic!("Invalid flow!");
+                },
+                Mark => {}
+            },
+            _ => {
+                self.print_diagnostic(state, patch);
+                panic!("Invalid flow!");
+            }
+        }
+    }
+
+    fn spread(&mut self, spread_direction: Direction, state: &FlowState,
+              patch: GroundState, new_pos: Coord,
+              nodes: &mut VecDeque<FlowState>, edges: &mut Vec<(Coord, Option<Coord>, Option<Coord>)>) {
+        let process = if spread_direction == West { SpreadingLeft } else { SpreadingRight };
+        match state.direction {
+            West | East => match patch {
+                Sand | WetSand => {
+                    self.mark_wet_sand(new_pos);
+                    nodes.push_back(FlowState::new(process, new_pos, South, state.edges_index));
+                },
+                Clay | Spring => {
+                    let edges_index = state.edges_index.unwrap();
+                    let (blocked_pos, left_pos, right_pos, seeking_edge) = match edges[edges_index] {
+                        (blocked, None, None) => (blocked, state.pos, state.pos, true),
+                        (blocked, Some(left_edge), None) => (blocked, left_edge, state.pos, false),
+                        (blocked, None, Some(right_edge)) => (blocked, state.pos, right_edge, false),
+                        (blocked, Some(left_edge), Some(right_edge)) => (blocked, left_edge, right_edge, false),
+                    };
+
+                    if seeking_edge {
+                        let edge = Some(state.pos);
+                        let edges_index = state.edges_index.unwrap();
+                        edges[edges_index] = if spread_direction == West { (blocked_pos, edge, None) } else { (blocked_pos, None, edge) };
+                    } else {
+                        let row = right_pos.row;
+                        for col in (left_pos.col)..=(right_pos.col) {
+                            self.patches.insert(Coord{row, col}, Water);
+                        }
+                        let next_pos = Coord{row: blocked_pos.row - 1, col: blocked_pos.col};
+                        self.mark_wet_sand(next_pos);
+
+                        edges[edges_index] = (next_pos, None, None);
+                        edges.push((next_pos, None, None));
+                        let new_edges_index = edges.len() - 1;
+                        nodes.push_back(FlowState::new(SpreadingLeft, next_pos, West, Some(new_edges_index)));
+                        nodes.push_back(FlowState::new(SpreadingRight, next_pos, East, Some(new_edges_index)));
+                    }
+                },
+                Water => {},
+                Mark => {}
+            },
+            South => match patch {
+                Sand | WetSand => {
+                    let edges_index = state.edges_index.unwrap();
+                    edges[edges_index] = (state.pos, None, None);
+                    self.mark_wet_sand(new_pos);
+                    nodes.push_back(FlowState::new(Flowing, new_pos, South, None));
+                },
+                Clay | Water => nodes.push_back(FlowState::new(process, state.pos, spread_direction, state.edges_index)),
+                Spring => {
+                    self.print_diagnostic(state, patch);
+                    panic!("Invalid flow!");
+                },
+                Mark => {}
+            },
+            _ => {
+                self.print_diagnostic(state, patch);
+                panic!("Invalid spread!");
+            }
+        }
+    }
+
+    pub fn open_spring(&mut self) -> u32 {
+        let mut nodes = VecDeque::new();
+        let mut edges = Vec::new();
+
+        nodes.push_back(FlowState::new(Flowing, self.spring, South, None));
+
+        while !nodes.is_empty() {
+            let state = nodes.pop_front().unwrap();
+            match self.step(state.pos, state.direction) {
+                None => {},
+                Some(target_pos) => {
+                    let patch = self.get_patch(target_pos);
+                    match state.process {
+                        Flowing => self.flow(&state, patch, target_pos, &mut nodes, &mut edges),
+                        SpreadingLeft => self.spread(West, &state, patch, target_pos, &mut nodes, &mut edges),
+                        SpreadingRight => self.spread(East, &state, patch, target_pos, &mut nodes, &mut edges),
+                    }
+                }
+            };
+        }
+
+        self.visited
+    }
+}
+
+impl fmt::Display for GroundScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let min_row = self.patches.keys().
+            map(|p| p.row).min().unwrap();
+        let max_row = self.patches.keys().
+            map(|p| p.row).max().unwrap();
+        let min_col = self.patches.keys().
+            map(|p| p.col).min().unwrap();
+        let max_col = self.patches.keys().
+            map(|p| p.col).max().unwrap();
+
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                match self.patches.get(&Coord{row, col}) {
+                    Some(ground_state) => {
+                        write!(f, "{}", ground_state)?;
+                    },
+                    None => write!(f, "{}", Sand)?,
+                };
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+pub struct WaterReached(pub u32);
+
+impl fmt::Display for WaterReached {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Spots Reached by Water: {}", self.0)
+    }
+}
+
+impl Answer for WaterReached {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+pub struct TrappedWater(pub usize);
+
+impl fmt::Display for TrappedWater {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Spots With Trapped Water: {}", self.0)
+    }
+}
+
+impl Answer for TrappedWater {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = GroundScan;
+    type Part1 = WaterReached;
+    type Part2 = TrappedWater;
+
+    fn parse(&self, input: &str) -> Result<GroundScan, ParseError> {
+        GroundScan::new(input, Coord{row: 0, col: 500})
+    }
+
+    fn part1(&self, ground_scan: &GroundScan) -> WaterReached {
+        WaterReached(ground_scan.clone().open_spring())
+    }
+
+    fn part2(&self, ground_scan: &GroundScan) -> TrappedWater {
+        let mut ground_scan = ground_scan.clone();
+        ground_scan.open_spring();
+        TrappedWater(ground_scan.trapped_spots_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_veins_on_either_axis() {
+        assert_eq!(parse_vein("x=495, y=2..7").unwrap(), ("x=", 495, (2, 7)));
+        assert_eq!(parse_vein("y=7, x=495..501").unwrap(), ("y=", 7, (495, 501)));
+        assert_eq!(parse_vein("x=5, y=3..3").unwrap(), ("x=", 5, (3, 3)));
+    }
+
+    #[test]
+    fn rejects_reversed_ranges() {
+        let e = parse_vein("x=5, y=3..1").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (8, "3..1"));
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day17;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day17::Day17, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,431 @@
+extern crate common;
+
+mod grapheme;
+
+use std::fmt;
+use std::collections::HashMap;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::parse_lines;
+
+pub use grapheme::graphemes;
+
+pub fn common_chars(a: &str, b: &str) -> String {
+    a.chars().zip(b.chars()).filter(|(x,y)| x == y).map(|(x,_)| x).collect()
+}
+
+pub fn chars_by_frequency(s: &str) -> HashMap<u32, Vec<char>> {
+    let mut frequencies = HashMap::new();
+    let mut result = HashMap::new();
+
+    for c in s.chars() {
+        let count = frequencies.entry(c).or_insert(0);
+        *count += 1;
+    }
+
+    for (c, f) in &frequencies {
+        let chars = result.entry(*f).or_insert_with(Vec::new);
+        chars.push(*c);
+    }
+
+    result
+}
+
+pub fn checksum(box_ids: &[String]) -> u32 {
+    ChecksumReport::new(box_ids, &[2, 3]).checksum() as u32
+}
+
+/// Groups the graphemes of `s` by how many times each occurs.
+pub fn graphemes_by_frequency(s: &str) -> HashMap<u32, Vec<&str>> {
+    let mut frequencies: HashMap<&str, u32> = HashMap::new();
+    let mut order = vec![];
+    let mut result = HashMap::new();
+
+    for g in graphemes(s) {
+        let count = frequencies.entry(g).or_insert(0);
+        if *count == 0 {
+            order.push(g);
+        }
+        *count += 1;
+    }
+
+    for g in order {
+        result.entry(frequencies[g]).or_insert_with(Vec::new).push(g);
+    }
+
+    result
+}
+
+/// The graphemes of one ID occurring exactly as often as each multiplicity of interest.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IdBreakdown {
+    pub id: String,
+    /// `(multiplicity, graphemes)`, in the order the multiplicities were given.
+    pub matches: Vec<(u32, Vec<String>)>,
+}
+
+/// How many IDs contain some grapheme exactly `m` times, for each multiplicity `m`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ChecksumReport {
+    pub counts: Vec<(u32, u32)>,
+    pub breakdown: Vec<IdBreakdown>,
+}
+
+impl ChecksumReport {
+    pub fn new(box_ids: &[String], multiplicities: &[u32]) -> ChecksumReport {
+        let mut counts: Vec<(u32, u32)> = multiplicities.iter().map(|&m| (m, 0)).collect();
+        let mut breakdown = vec![];
+
+        for box_id in box_ids {
+            let by_freq = graphemes_by_frequency(box_id);
+            let mut matches = vec![];
+            for (m, count) in counts.iter_mut() {
+                if let Some(gs) = by_freq.get(m) {
+                    *count += 1;
+                    matches.push((*m, gs.iter().map(|g| g.to_string()).collect()));
+                }
+            }
+            breakdown.push(IdBreakdown { id: box_id.clone(), matches });
+        }
+
+        ChecksumReport { counts, breakdown }
+    }
+
+    /// The product of the per-multiplicity counts.
+    pub fn checksum(&self) -> u64 {
+        self.counts.iter().map(|&(_, count)| u64::from(count)).product()
+    }
+}
+
+/// Two box IDs of equal length that differ in at most a few positions.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NearDuplicate {
+    /// Indices of the two IDs, `first < second`.
+    pub first: usize,
+    pub second: usize,
+    /// Grapheme positions at which the IDs differ.
+    pub positions: Vec<usize>,
+}
+
+/// The range of `block` when an ID of `len` graphemes is cut into `blocks` pieces.
+fn block_range(len: usize, blocks: usize, block: usize) -> std::ops::Range<usize> {
+    (block * len / blocks)..((block + 1) * len / blocks)
+}
+
+/// Finds every pair of box IDs within Hamming distance `max_distance`, counted in
+/// graphemes, ordered by index.
+///
+/// Cutting each ID into `max_distance + 1` blocks guarantees that a matching pair agrees
+/// on a whole block, so only IDs sharing a block are compared; each pair is reported
+/// from the first block it agrees on.
+pub fn near_duplicates(box_ids: &[String], max_distance: usize) -> Vec<NearDuplicate> {
+    let blocks = max_distance + 1;
+    let ids: Vec<Vec<&str>> = box_ids.iter().map(|id| graphemes(id)).collect();
+    let mut buckets: HashMap<(usize, usize, &[&str]), Vec<usize>> = HashMap::new();
+
+    for (i, id) in ids.iter().enumerate() {
+        for block in 0..blocks {
+            let key = (id.len(), block, &id[block_range(id.len(), blocks, block)]);
+            buckets.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut matches = vec![];
+    for (&(len, block, _), members) in &buckets {
+        for (n, &first) in members.iter().enumerate() {
+            for &second in &members[(n+1)..] {
+                let (a, b) = (&ids[first], &ids[second]);
+                let positions: Vec<usize> = (0..len).filter(|&p| a[p] != b[p]).collect();
+                if positions.len() > max_distance {
+                    continue;
+                }
+
+                let first_shared = (0..blocks).find(|&other| {
+                    let range = block_range(len, blocks, other);
+                    !positions.iter().any(|p| range.contains(p))
+                });
+                if first_shared == Some(block) {
+                    matches.push(NearDuplicate { first, second, positions });
+                }
+            }
+        }
+    }
+
+    matches.sort_by_key(|m| (m.first, m.second));
+    matches
+}
+
+/// Finds the graphemes shared by the two box IDs that differ in exactly one position.
+pub fn common_box_id(box_ids: &[String]) -> Option<String> {
+    near_duplicates(box_ids, 1).into_iter().
+        find(|m| m.positions.len() == 1).
+        map(|m| {
+            graphemes(&box_ids[m.first]).into_iter().
+                enumerate().
+                filter(|(p, _)| !m.positions.contains(p)).
+                map(|(_, g)| g).
+                collect()
+        })
+}
+
+/// A pair of box IDs within a small edit distance of each other.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FuzzyMatch {
+    pub first: usize,
+    pub second: usize,
+    pub distance: usize,
+    /// The graphemes the two IDs keep in common along a cheapest alignment.
+    pub common: String,
+}
+
+/// The Levenshtein table for `a` against `b`: entry `[i][j]` is the number of insertions,
+/// deletions and substitutions turning the first `i` graphemes of `a` into the first `j` of `b`.
+fn edit_table(a: &[&str], b: &[&str]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    table[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = table[i-1][j-1] + if a[i-1] == b[j-1] { 0 } else { 1 };
+            table[i][j] = substitution.min(table[i-1][j] + 1).min(table[i][j-1] + 1);
+        }
+    }
+    table
+}
+
+/// The Levenshtein distance between `a` and `b`, counted in graphemes.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (graphemes(a), graphemes(b));
+    edit_table(&a, &b)[a.len()][b.len()]
+}
+
+/// The edit distance between `a` and `b`, and the graphemes they share along a cheapest
+/// alignment, e.g. "abde" for "abcde" and "abde".
+pub fn aligned_common_chars(a: &str, b: &str) -> (usize, String) {
+    let (a, b) = (graphemes(a), graphemes(b));
+    let table = edit_table(&a, &b);
+
+    let mut common = vec![];
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 && j > 0 {
+        if a[i-1] == b[j-1] && table[i][j] == table[i-1][j-1] {
+            common.push(a[i-1]);
+            i -= 1;
+            j -= 1;
+        } else if table[i][j] == table[i-1][j-1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if table[i][j] == table[i-1][j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    common.reverse();
+    (table[a.len()][b.len()], common.into_iter().collect())
+}
+
+/// Finds every pair of box IDs within edit distance `max_distance`, ordered by index.
+/// Unlike `near_duplicates`, IDs of different lengths can match.
+pub fn fuzzy_matches(box_ids: &[String], max_distance: usize) -> Vec<FuzzyMatch> {
+    let lengths: Vec<usize> = box_ids.iter().map(|id| graphemes(id).len()).collect();
+    let mut matches = vec![];
+
+    for (first, box_id_one) in box_ids.iter().enumerate() {
+        for (second, box_id_two) in box_ids.iter().enumerate().skip(first + 1) {
+            if lengths[first].abs_diff(lengths[second]) > max_distance {
+                continue;
+            }
+            let (distance, common) = aligned_common_chars(box_id_one, box_id_two);
+            if distance <= max_distance {
+                matches.push(FuzzyMatch { first, second, distance, common });
+            }
+        }
+    }
+
+    matches
+}
+
+/// How part 2 decides that two box IDs are the pair of prototype boxes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Matching {
+    /// Equal length, differing in exactly one position.
+    Hamming,
+    /// One insertion, deletion or substitution apart.
+    Levenshtein,
+}
+
+pub struct Checksum(pub ChecksumReport);
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Checksum = {}", self.0.checksum())
+    }
+}
+
+impl Answer for Checksum {
+    fn value(&self) -> Json {
+        Json::from(self.0.checksum())
+    }
+
+    /// The count for each multiplicity, and the graphemes each ID matched them with.
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let counts = self.0.counts.iter().
+            map(|&(m, count)| (m.to_string(), Json::from(count))).
+            collect();
+        let breakdown = self.0.breakdown.iter().
+            map(|b| {
+                let matches = b.matches.iter().
+                    map(|(m, gs)| (m.to_string(), Json::Array(gs.iter().map(|g| Json::from(g.as_str())).collect()))).
+                    collect();
+                Json::object(vec![("id", Json::from(b.id.as_str())), ("matches", Json::Object(matches))])
+            }).
+            collect();
+        vec![("counts", Json::Object(counts)), ("breakdown", Json::Array(breakdown))]
+    }
+}
+
+/// The graphemes the two prototype box IDs share, unless no pair of IDs matches.
+pub struct CommonBoxId(pub Option<String>);
+
+impl fmt::Display for CommonBoxId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(ref common) => write!(f, "{}", common),
+            None => write!(f, "No two box IDs differ by one grapheme"),
+        }
+    }
+}
+
+impl Answer for CommonBoxId {
+    fn value(&self) -> Json {
+        Json::from(self.0.as_deref())
+    }
+}
+
+pub struct Day2 {
+    /// Grapheme multiplicities whose ID counts multiply into the checksum.
+    pub multiplicities: Vec<u32>,
+    pub matching: Matching,
+}
+
+impl Default for Day2 {
+    fn default() -> Day2 {
+        Day2{multiplicities: vec![2, 3], matching: Matching::Hamming}
+    }
+}
+
+impl Solver for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<String>;
+    type Part1 = Checksum;
+    type Part2 = CommonBoxId;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input, |l| {
+            let box_id = l.trim();
+            match box_id.find(char::is_whitespace) {
+                Some(i) => Err(ParseError::at(l, &box_id[i..], "a box id without whitespace")),
+                None => Ok(box_id.to_string()),
+            }
+        })
+    }
+
+    fn part1(&self, box_ids: &Vec<String>) -> Checksum {
+        Checksum(ChecksumReport::new(box_ids, &self.multiplicities))
+    }
+
+    fn part2(&self, box_ids: &Vec<String>) -> CommonBoxId {
+        let common = match self.matching {
+            Matching::Hamming => common_box_id(box_ids),
+            Matching::Levenshtein => fuzzy_matches(box_ids, 1).into_iter().
+                find(|m| m.distance == 1).
+                map(|m| m.common),
+        };
+        CommonBoxId(common)
+    }
+
+    /// Takes the checksum multiplicities as a comma separated list, e.g. 2,3,4, then
+    /// the part 2 matching, hamming or levenshtein.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        if let Some(param) = params.first() {
+            self.multiplicities = param.split(',').
+                map(|m| common::parse_param(m.trim(), "multiplicity")).
+                collect::<Result<_, _>>()?;
+        }
+        if let Some(param) = params.get(1) {
+            self.matching = match param.as_str() {
+                "hamming" => Matching::Hamming,
+                "levenshtein" => Matching::Levenshtein,
+                _ => return Err(format!("Invalid matching: {} (expected hamming or levenshtein)", param)),
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    /// Every pair of equal length IDs within `max_distance`, by comparing all of them.
+    fn scan(box_ids: &[String], max_distance: usize) -> Vec<NearDuplicate> {
+        let ids: Vec<Vec<&str>> = box_ids.iter().map(|id| graphemes(id)).collect();
+        let mut matches = vec![];
+        for first in 0..ids.len() {
+            for second in first + 1..ids.len() {
+                let (a, b) = (&ids[first], &ids[second]);
+                if a.len() != b.len() {
+                    continue;
+                }
+                let positions: Vec<usize> = (0..a.len()).filter(|&p| a[p] != b[p]).collect();
+                if positions.len() <= max_distance {
+                    matches.push(NearDuplicate { first, second, positions });
+                }
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn near_duplicates_match_a_scan_of_every_pair() {
+        let mut rng = Rng::new(17);
+        // A small alphabet and a few lengths make pairs at every distance common.
+        let box_ids: Vec<String> = (0..200).map(|_| {
+            let len = 4 + rng.below(3);
+            (0..len).map(|_| ["a", "b", "c", "é"][rng.below(4) as usize]).collect()
+        }).collect();
+
+        for max_distance in 0..5 {
+            assert_eq!(near_duplicates(&box_ids, max_distance), scan(&box_ids, max_distance),
+                       "max distance {}", max_distance);
+        }
+    }
+
+    #[test]
+    fn checksum_counts_graphemes_of_every_script() {
+        let box_ids = vec!["किकीx".to_string(), "กำกำ".to_string()];
+        let report = ChecksumReport::new(&box_ids, &[2]);
+        assert_eq!(report.counts, vec![(2, 1)]);
+        assert!(report.breakdown[0].matches.is_empty());
+        assert_eq!(report.breakdown[1].matches, vec![(2, vec!["กำ".to_string()])]);
+    }
+
+    #[test]
+    fn near_duplicates_count_positions_in_graphemes() {
+        let box_ids: Vec<String> = ["🇫🇷x👨‍👩‍👧y", "🇩🇪x👨‍👩‍👦y", "🇫🇷x👨‍👩‍👧y", "🇫🇷xy"].
+            iter().map(|id| id.to_string()).collect();
+        let matches = near_duplicates(&box_ids, 2);
+        assert_eq!(matches, vec![
+            NearDuplicate { first: 0, second: 1, positions: vec![0, 2] },
+            NearDuplicate { first: 0, second: 2, positions: vec![] },
+            NearDuplicate { first: 1, second: 2, positions: vec![0, 2] },
+        ]);
+        assert_eq!(common_box_id(&box_ids[..2]), None);
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day2;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day2::Day2::default, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,664 @@
+extern crate common;
+
+use std::fmt;
+use std::collections::HashMap;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::image::{Image, Rgb};
+use common::parse::{parse_lines, parse_number, parse_pair};
+
+#[derive(Copy,Clone)]
+pub struct Rectangle {
+    pub id: usize,
+    pub left: usize,
+    pub top: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rectangle {
+    pub fn parse(rectangle: &str) -> Result<Rectangle, ParseError> {
+        let tokens: Vec<&str> = rectangle.split_whitespace().collect();
+        if tokens.len() != 4 || !tokens[0].starts_with('#') || tokens[1] != "@" || !tokens[2].ends_with(':') {
+            return Err(ParseError::at(rectangle, rectangle.trim(), "#<id> @ <left>,<top>: <width>x<height>"));
+        }
+
+        let id = parse_number(rectangle, &tokens[0][1..], "a claim id")?;
+        let (left, top) = parse_pair(rectangle, tokens[2].trim_end_matches(':'), ',', "<left>,<top>")?;
+        let (width, height) = parse_pair(rectangle, tokens[3], 'x', "<width>x<height>")?;
+
+        Ok(Rectangle {id, left, top, width, height })
+    }
+
+    pub fn right(&self) -> usize {
+        self.left + self.width
+    }
+
+    pub fn bottom(&self) -> usize {
+        self.top + self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Whether the two claims share any square inch.
+    pub fn overlaps(&self, other: &Rectangle) -> bool {
+        !self.is_empty() && !other.is_empty() &&
+            self.left < other.right() && other.left < self.right() &&
+            self.top < other.bottom() && other.top < self.bottom()
+    }
+
+    /// Whether the square inch at `(x, y)` lies within the claim.
+    pub fn covers(&self, x: usize, y: usize) -> bool {
+        self.left <= x && x < self.right() && self.top <= y && y < self.bottom()
+    }
+
+    /// The area shared with `other`, keeping this claim's id.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let left = self.left.max(other.left);
+        let top = self.top.max(other.top);
+        let width = self.right().min(other.right()) - left;
+        let height = self.bottom().min(other.bottom()) - top;
+        Some(Rectangle { id: self.id, left, top, width, height })
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} @ {},{}: {}x{}", self.id, self.left, self.top, self.width, self.height)
+    }
+}
+
+/// Segment tree over the gaps between sorted y coordinates, tracking how much of the
+/// column is covered by at least one and by at least two of the active claims.
+struct CoverTree {
+    ys: Vec<usize>,
+    count: Vec<u32>,
+    once: Vec<u64>,
+    twice: Vec<u64>,
+}
+
+impl CoverTree {
+    fn new(ys: Vec<usize>) -> CoverTree {
+        let nodes = 4 * ys.len().max(1);
+        CoverTree { ys, count: vec![0; nodes], once: vec![0; nodes], twice: vec![0; nodes] }
+    }
+
+    /// Adds `delta` to the cover count of the rows `top..bottom`.
+    fn add(&mut self, top: usize, bottom: usize, delta: i32) {
+        let from = self.ys.binary_search(&top).unwrap();
+        let to = self.ys.binary_search(&bottom).unwrap();
+        let segments = self.ys.len() - 1;
+        self.update(1, 0, segments, from, to, delta);
+    }
+
+    fn update(&mut self, node: usize, lo: usize, hi: usize, from: usize, to: usize, delta: i32) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.count[node] = (self.count[node] as i32 + delta) as u32;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update(2 * node, lo, mid, from, to, delta);
+            self.update(2 * node + 1, mid, hi, from, to, delta);
+        }
+        self.recompute(node, lo, hi);
+    }
+
+    fn recompute(&mut self, node: usize, lo: usize, hi: usize) {
+        let full = (self.ys[hi] - self.ys[lo]) as u64;
+        let leaf = hi - lo == 1;
+        let (child_once, child_twice) = if leaf {
+            (0, 0)
+        } else {
+            (self.once[2 * node] + self.once[2 * node + 1], self.twice[2 * node] + self.twice[2 * node + 1])
+        };
+
+        match self.count[node] {
+            0 => {
+                self.once[node] = child_once;
+                self.twice[node] = child_twice;
+            },
+            1 => {
+                self.once[node] = full;
+                self.twice[node] = child_once;
+            },
+            _ => {
+                self.once[node] = full;
+                self.twice[node] = full;
+            },
+        }
+    }
+
+    fn covered_twice(&self) -> u64 {
+        self.twice[1]
+    }
+}
+
+/// A set of claims, answering overlap questions without laying out the fabric, so
+/// coordinates can run up to `u32::MAX`.
+pub struct ClaimSet {
+    claims: Vec<Rectangle>,
+}
+
+impl ClaimSet {
+    pub fn new(claims: &[Rectangle]) -> ClaimSet {
+        ClaimSet { claims: claims.to_vec() }
+    }
+
+    pub fn claims(&self) -> &[Rectangle] {
+        &self.claims
+    }
+
+    /// Area claimed more than once, swept left to right over the claims' vertical edges.
+    pub fn overclaimed_area(&self) -> u64 {
+        let claims: Vec<&Rectangle> = self.claims.iter().filter(|c| !c.is_empty()).collect();
+
+        let mut ys: Vec<usize> = claims.iter().flat_map(|c| vec![c.top, c.bottom()]).collect();
+        ys.sort_unstable();
+        ys.dedup();
+        if ys.len() < 2 {
+            return 0;
+        }
+
+        let mut edges: Vec<(usize, i32, &Rectangle)> = claims.iter().
+            flat_map(|&c| vec![(c.left, 1, c), (c.right(), -1, c)]).
+            collect();
+        edges.sort_by_key(|&(x, delta, _)| (x, delta));
+
+        let mut tree = CoverTree::new(ys);
+        let mut area = 0;
+        let mut last_x = 0;
+        for (x, delta, claim) in edges {
+            area += tree.covered_twice() * (x - last_x) as u64;
+            tree.add(claim.top, claim.bottom(), delta);
+            last_x = x;
+        }
+
+        area
+    }
+
+    /// Claims that overlap no other claim, in input order.
+    pub fn intact_claims(&self) -> Vec<&Rectangle> {
+        let mut by_left: Vec<usize> = (0..self.claims.len()).collect();
+        by_left.sort_by_key(|&i| self.claims[i].left);
+
+        // Sweep left to right, keeping the claims whose span still reaches the sweep.
+        let mut overlapped = vec![false; self.claims.len()];
+        let mut active: Vec<usize> = vec![];
+        for &i in &by_left {
+            let claim = &self.claims[i];
+            active.retain(|&j| self.claims[j].right() > claim.left);
+            for &j in &active {
+                if claim.overlaps(&self.claims[j]) {
+                    overlapped[i] = true;
+                    overlapped[j] = true;
+                }
+            }
+            if !claim.is_empty() {
+                active.push(i);
+            }
+        }
+
+        self.claims.iter().zip(overlapped).
+            filter(|&(_, overlapped)| !overlapped).
+            map(|(claim, _)| claim).
+            collect()
+    }
+}
+
+/// Longest side of a heatmap; larger fabrics are scaled down to fit.
+const HEATMAP_MAX_SIDE: usize = 2048;
+
+const UNCLAIMED: Rgb = [16, 16, 24];
+const CLAIMED_ONCE: Rgb = [40, 70, 170];
+const INTACT: Rgb = [30, 200, 90];
+
+/// Yellow for two claims, reddening towards the most claimed square inch.
+fn overclaimed_colour(count: u32, max_count: u32) -> Rgb {
+    let share = if max_count > 2 { f64::from(count - 2) / f64::from(max_count - 2) } else { 0.0 };
+    [255, (220.0 * (1.0 - share)) as u8, 0]
+}
+
+impl ClaimSet {
+    /// Draws the fabric under the claims, coloured by how many claims cover each square
+    /// inch, with intact claims in green. A fabric wider or taller than
+    /// `HEATMAP_MAX_SIDE` is scaled down, each pixel counting the claims touching it.
+    pub fn heatmap(&self) -> Image {
+        let claims: Vec<&Rectangle> = self.claims.iter().filter(|c| !c.is_empty()).collect();
+        if claims.is_empty() {
+            return Image::new(1, 1, UNCLAIMED);
+        }
+
+        let left = claims.iter().map(|c| c.left).min().unwrap();
+        let top = claims.iter().map(|c| c.top).min().unwrap();
+        let right = claims.iter().map(|c| c.right()).max().unwrap();
+        let bottom = claims.iter().map(|c| c.bottom()).max().unwrap();
+
+        let scale = (right - left).max(bottom - top).div_ceil(HEATMAP_MAX_SIDE);
+        let width = (right - left).div_ceil(scale);
+        let height = (bottom - top).div_ceil(scale);
+        let pixels = |c: &Rectangle| {
+            let cols = (c.left - left) / scale..=(c.right() - 1 - left) / scale;
+            let rows = (c.top - top) / scale..=(c.bottom() - 1 - top) / scale;
+            (cols, rows)
+        };
+
+        let mut counts = vec![0u32; width * height];
+        for claim in &claims {
+            let (cols, rows) = pixels(claim);
+            for y in rows {
+                for x in cols.clone() {
+                    counts[y * width + x] += 1;
+                }
+            }
+        }
+
+        let max_count = counts.iter().copied().max().unwrap_or(0);
+        let mut image = Image::new(width, height, UNCLAIMED);
+        for y in 0..height {
+            for x in 0..width {
+                match counts[y * width + x] {
+                    0 => {},
+                    1 => image.set(x, y, CLAIMED_ONCE),
+                    count => image.set(x, y, overclaimed_colour(count, max_count)),
+                }
+            }
+        }
+
+        for claim in self.intact_claims().into_iter().filter(|c| !c.is_empty()) {
+            let (cols, rows) = pixels(claim);
+            for y in rows {
+                for x in cols.clone() {
+                    image.set(x, y, INTACT);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+/// Two overlapping claims and the area they share.
+pub struct Overlap {
+    pub first: Rectangle,
+    pub second: Rectangle,
+    pub intersection: Rectangle,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let area = &self.intersection;
+        write!(f, "{} and {} share {},{}: {}x{}",
+               self.first, self.second, area.left, area.top, area.width, area.height)
+    }
+}
+
+const NODE_CAPACITY: usize = 8;
+
+/// The box around the entries of an index node, as half open ranges.
+#[derive(Copy, Clone)]
+struct Bounds {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+impl Bounds {
+    fn of(claim: &Rectangle) -> Bounds {
+        Bounds { left: claim.left, top: claim.top, right: claim.right(), bottom: claim.bottom() }
+    }
+
+    fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.left == self.right || self.top == self.bottom
+    }
+
+    fn intersects(&self, other: &Bounds) -> bool {
+        !self.is_empty() && !other.is_empty() &&
+            self.left < other.right && other.left < self.right &&
+            self.top < other.bottom && other.top < self.bottom
+    }
+
+    fn center(&self) -> (usize, usize) {
+        (self.left + (self.right - self.left) / 2, self.top + (self.bottom - self.top) / 2)
+    }
+}
+
+struct Node {
+    bounds: Bounds,
+    /// Indices into the claims for a leaf, otherwise into the nodes.
+    children: Vec<usize>,
+    leaf: bool,
+}
+
+/// An R-tree over claims, packed bottom up by sort-tile-recursive grouping.
+pub struct ClaimIndex {
+    claims: Vec<Rectangle>,
+    by_id: HashMap<usize, usize>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl ClaimIndex {
+    pub fn new(claims: &[Rectangle]) -> ClaimIndex {
+        let claims = claims.to_vec();
+        let by_id = claims.iter().enumerate().map(|(i, c)| (c.id, i)).collect();
+
+        let mut nodes = vec![];
+        let mut level: Vec<(Bounds, usize)> = claims.iter().enumerate().
+            filter(|(_, c)| !c.is_empty()).
+            map(|(i, c)| (Bounds::of(c), i)).
+            collect();
+        let mut leaf = true;
+
+        let root = loop {
+            if level.is_empty() {
+                break None;
+            }
+            if level.len() == 1 && !leaf {
+                break Some(level[0].1);
+            }
+
+            let mut parents = vec![];
+            for group in ClaimIndex::tile(level) {
+                let bounds = group.iter().skip(1).fold(group[0].0, |b, (other, _)| b.union(other));
+                nodes.push(Node { bounds, children: group.iter().map(|&(_, i)| i).collect(), leaf });
+                parents.push((bounds, nodes.len() - 1));
+            }
+            level = parents;
+            leaf = false;
+        };
+
+        ClaimIndex { claims, by_id, nodes, root }
+    }
+
+    /// Groups entries that lie near each other: vertical slices by x, then runs by y.
+    fn tile(mut entries: Vec<(Bounds, usize)>) -> Vec<Vec<(Bounds, usize)>> {
+        let groups = entries.len().div_ceil(NODE_CAPACITY);
+        let slices = (groups as f64).sqrt().ceil() as usize;
+        let slice_len = slices * NODE_CAPACITY;
+
+        entries.sort_by_key(|(b, _)| b.center().0);
+        let mut tiles = vec![];
+        for slice in entries.chunks_mut(slice_len) {
+            slice.sort_by_key(|(b, _)| b.center().1);
+            tiles.extend(slice.chunks(NODE_CAPACITY).map(|group| group.to_vec()));
+        }
+        tiles
+    }
+
+    /// Indices of the claims whose bounds intersect `area`, in input order.
+    fn search(&self, area: &Bounds) -> Vec<usize> {
+        let mut found = vec![];
+        let mut pending: Vec<usize> = self.root.into_iter().collect();
+
+        while let Some(n) = pending.pop() {
+            let node = &self.nodes[n];
+            if !node.bounds.intersects(area) {
+                continue;
+            }
+            if node.leaf {
+                found.extend(node.children.iter().filter(|&&i| Bounds::of(&self.claims[i]).intersects(area)));
+            } else {
+                pending.extend(&node.children);
+            }
+        }
+
+        found.sort_unstable();
+        found
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Rectangle> {
+        self.by_id.get(&id).map(|&i| &self.claims[i])
+    }
+
+    /// The other claims sharing area with claim `id`; `None` for an unknown id.
+    pub fn overlapping(&self, id: usize) -> Option<Vec<&Rectangle>> {
+        let &index = self.by_id.get(&id)?;
+        let claim = &self.claims[index];
+        Some(self.search(&Bounds::of(claim)).into_iter().
+             filter(|&i| i != index).
+             map(|i| &self.claims[i]).
+             collect())
+    }
+
+    /// The claims covering the square inch at `(x, y)`.
+    pub fn covering(&self, x: usize, y: usize) -> Vec<&Rectangle> {
+        let point = Bounds { left: x, top: y, right: x + 1, bottom: y + 1 };
+        self.search(&point).into_iter().map(|i| &self.claims[i]).collect()
+    }
+
+    /// Every pair of overlapping claims with the area they share, in input order.
+    pub fn overlapping_pairs(&self) -> Vec<Overlap> {
+        let mut overlaps = vec![];
+        for (i, first) in self.claims.iter().enumerate() {
+            for j in self.search(&Bounds::of(first)).into_iter().filter(|&j| j > i) {
+                let second = self.claims[j];
+                if let Some(intersection) = first.intersection(&second) {
+                    overlaps.push(Overlap { first: *first, second, intersection });
+                }
+            }
+        }
+        overlaps
+    }
+}
+
+/// The id of the first claim overlapping no other, unless every claim overlaps another.
+pub struct IntactClaim(pub Option<usize>);
+
+impl fmt::Display for IntactClaim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(id) => write!(f, "{}", id),
+            None => write!(f, "Every claim overlaps another"),
+        }
+    }
+}
+
+impl Answer for IntactClaim {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+#[derive(Default)]
+pub struct Day3 {
+    /// Where to save a heatmap of the fabric, as PNG or PPM by extension, if anywhere.
+    pub heatmap: Option<String>,
+}
+
+impl Solver for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Rectangle>;
+    type Part1 = u64;
+    type Part2 = IntactClaim;
+
+    fn parse(&self, input: &str) -> Result<Vec<Rectangle>, ParseError> {
+        parse_lines(input, Rectangle::parse)
+    }
+
+    fn part1(&self, claims: &Vec<Rectangle>) -> u64 {
+        ClaimSet::new(claims).overclaimed_area()
+    }
+
+    fn part2(&self, claims: &Vec<Rectangle>) -> IntactClaim {
+        IntactClaim(ClaimSet::new(claims).intact_claims().first().map(|claim| claim.id))
+    }
+
+    /// Takes a file to save the part 1 heatmap to, ending .png for PNG, otherwise PPM.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        self.heatmap = params.first().cloned();
+        Ok(())
+    }
+
+    /// Saves the part 1 heatmap, when asked for.
+    fn write_outputs(&self, claims: &Vec<Rectangle>, part: u32) -> Result<(), String> {
+        match self.heatmap {
+            Some(ref path) if part == 1 => ClaimSet::new(claims).heatmap().save(path).
+                map_err(|e| format!("{}: {}", path, e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    /// Claims from a small linear congruential generator, every seventh one empty.
+    fn random_claims(count: usize, seed: u64) -> Vec<Rectangle> {
+        let mut rng = Rng::new(seed);
+        let mut next = |bound: u64| rng.below(bound) as usize;
+        (0..count).map(|i| {
+            let (left, top) = (next(100), next(100));
+            let (width, height) = if i % 7 == 3 { (next(2) * 5, 0) } else { (1 + next(20), 1 + next(20)) };
+            Rectangle { id: i + 1, left, top, width, height }
+        }).collect()
+    }
+
+    fn ids<'a, I: IntoIterator<Item = &'a Rectangle>>(claims: I) -> Vec<usize> {
+        claims.into_iter().map(|c| c.id).collect()
+    }
+
+    fn check_against_scan(claims: &[Rectangle]) {
+        let index = ClaimIndex::new(claims);
+
+        for claim in claims {
+            let expected = ids(claims.iter().filter(|c| c.id != claim.id && c.overlaps(claim)));
+            assert_eq!(ids(index.overlapping(claim.id).unwrap()), expected, "claim {}", claim);
+        }
+
+        for y in 0..125 {
+            for x in 0..125 {
+                let expected = ids(claims.iter().filter(|c| c.covers(x, y)));
+                assert_eq!(ids(index.covering(x, y)), expected, "at {},{}", x, y);
+            }
+        }
+
+        let mut expected = vec![];
+        for (i, first) in claims.iter().enumerate() {
+            for second in &claims[i + 1..] {
+                if let Some(area) = first.intersection(second) {
+                    expected.push((first.id, second.id, area.left, area.top, area.width, area.height));
+                }
+            }
+        }
+        let pairs: Vec<_> = index.overlapping_pairs().iter().
+            map(|o| (o.first.id, o.second.id, o.intersection.left, o.intersection.top,
+                     o.intersection.width, o.intersection.height)).
+            collect();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn index_matches_a_scan_of_every_claim() {
+        for &(count, seed) in &[(0, 1), (5, 2), (9, 3), (70, 4), (300, 5)] {
+            check_against_scan(&random_claims(count, seed));
+        }
+    }
+
+    #[test]
+    fn index_of_a_single_claim() {
+        let claim = Rectangle { id: 7, left: 3, top: 2, width: 4, height: 5 };
+        let index = ClaimIndex::new(&[claim]);
+        assert_eq!(index.overlapping(7).map(ids), Some(vec![]));
+        assert_eq!(ids(index.covering(3, 2)), vec![7]);
+        assert_eq!(ids(index.covering(6, 6)), vec![7]);
+        assert!(index.covering(7, 6).is_empty());
+        assert!(index.overlapping_pairs().is_empty());
+        assert!(index.overlapping(8).is_none());
+    }
+
+    #[test]
+    fn empty_claims_overlap_nothing() {
+        let claims = [
+            Rectangle { id: 1, left: 0, top: 0, width: 10, height: 10 },
+            Rectangle { id: 2, left: 2, top: 2, width: 0, height: 4 },
+            Rectangle { id: 3, left: 4, top: 4, width: 3, height: 0 },
+        ];
+        let index = ClaimIndex::new(&claims);
+        assert_eq!(index.overlapping(1).map(ids), Some(vec![]));
+        assert_eq!(index.overlapping(2).map(ids), Some(vec![]));
+        assert_eq!(ids(index.covering(2, 2)), vec![1]);
+        assert!(index.overlapping_pairs().is_empty());
+        assert_eq!(index.get(3).map(|c| c.width), Some(3));
+
+        let index = ClaimIndex::new(&claims[1..]);
+        assert!(index.covering(2, 2).is_empty());
+        assert_eq!(index.overlapping(3).map(ids), Some(vec![]));
+    }
+
+    #[test]
+    fn tiles_hold_every_entry_at_most_a_node_each() {
+        let entries: Vec<(Bounds, usize)> = random_claims(300, 6).iter().
+            enumerate().
+            map(|(i, c)| (Bounds::of(c), i)).
+            collect();
+        let tiles = ClaimIndex::tile(entries);
+        assert!(tiles.iter().all(|t| !t.is_empty() && t.len() <= NODE_CAPACITY));
+
+        let mut indices: Vec<usize> = tiles.iter().flatten().map(|&(_, i)| i).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..300).collect::<Vec<_>>());
+    }
+
+    fn claim(id: usize, left: usize, top: usize, width: usize, height: usize) -> Rectangle {
+        Rectangle { id, left, top, width, height }
+    }
+
+    #[test]
+    fn heatmap_colours_the_example() {
+        let claims = vec![claim(1, 1, 3, 4, 4), claim(2, 3, 1, 4, 4), claim(3, 5, 5, 2, 2)];
+        let image = ClaimSet::new(&claims).heatmap();
+        assert_eq!((image.width(), image.height()), (6, 6));
+        assert_eq!(image.get(0, 0), UNCLAIMED);
+        assert_eq!(image.get(0, 2), CLAIMED_ONCE);
+        assert_eq!(image.get(5, 0), CLAIMED_ONCE);
+        assert_eq!(image.get(2, 2), overclaimed_colour(2, 2));
+        assert_eq!(image.get(3, 3), [255, 220, 0]);
+        for &(x, y) in &[(4, 4), (5, 4), (4, 5), (5, 5)] {
+            assert_eq!(image.get(x, y), INTACT, "{}, {}", x, y);
+        }
+    }
+
+    #[test]
+    fn heatmap_scales_down_u32_coordinates() {
+        let far = u32::MAX as usize;
+        let mut claims = vec![
+            claim(1, 0, 0, 5, 5),
+            claim(2, far - 295, 4_000_000_000, 295, 10),
+            claim(3, far - 95, 4_000_000_005, 95, 5),
+        ];
+        let image = ClaimSet::new(&claims).heatmap();
+        // Each pixel covers 2^21 square inches, the fewest that fit 2^32 - 1 into 2048.
+        assert_eq!((image.width(), image.height()), (HEATMAP_MAX_SIDE, 1908));
+        assert_eq!(image.get(0, 0), INTACT);
+        assert_eq!(image.get(2047, 1907), overclaimed_colour(2, 2));
+        assert_eq!(image.get(1, 0), UNCLAIMED);
+        assert_eq!(image.get(2047, 1906), UNCLAIMED);
+
+        // An intact claim is drawn over the overlap it shares a pixel with.
+        claims.push(claim(4, far - 295, 4_000_000_100, 1, 1));
+        let image = ClaimSet::new(&claims).heatmap();
+        assert_eq!(image.get(2047, 1907), INTACT);
+        assert_eq!(image.get(0, 0), INTACT);
+    }
+
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day3;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day3::Day3::default, env!("CARGO_MANIFEST_DIR"));
+}
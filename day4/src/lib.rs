@@ -0,0 +1,760 @@
+extern crate common;
+
+use std::fmt;
+use std::io::{self, Write};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::{parse_number, parse_pair};
+
+pub enum ShiftState {
+    Start,
+    Awake,
+    Asleep,
+}
+
+#[derive(Copy,Clone,Debug,Eq,PartialEq,PartialOrd,Ord)]
+pub struct Timestamp {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+    pub hour: usize,
+    pub min: usize,
+}
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+pub fn is_leap_year(year: usize) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+pub fn days_in_month(year: usize, month: usize) -> usize {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+impl Timestamp {
+    /// A timestamp on the proleptic Gregorian calendar, or `None` if any field is out of range.
+    pub fn new(year: usize, month: usize, day: usize, hour: usize, min: usize) -> Option<Timestamp> {
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) &&
+            hour < 24 && min < 60;
+        if valid { Some(Timestamp{year, month, day, hour, min}) } else { None }
+    }
+
+    /// Minutes since 0000-03-01 00:00, counting days as in Howard Hinnant's `days_from_civil`.
+    pub fn to_minutes(&self) -> i64 {
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era;
+
+        days * MINUTES_PER_DAY + self.hour as i64 * 60 + self.min as i64
+    }
+
+    /// The inverse of `to_minutes`.
+    pub fn from_minutes(minutes: i64) -> Timestamp {
+        let days = minutes.div_euclid(MINUTES_PER_DAY);
+        let min_of_day = minutes.rem_euclid(MINUTES_PER_DAY);
+
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Timestamp {
+            year: year as usize,
+            month: month as usize,
+            day: day as usize,
+            hour: (min_of_day / 60) as usize,
+            min: (min_of_day % 60) as usize,
+        }
+    }
+
+    pub fn add_minutes(&self, minutes: i64) -> Timestamp {
+        Timestamp::from_minutes(self.to_minutes() + minutes)
+    }
+
+    /// Minutes from this timestamp to `later`, negative if `later` is earlier.
+    pub fn minutes_until(&self, later: &Timestamp) -> i64 {
+        later.to_minutes() - self.to_minutes()
+    }
+}
+
+pub struct LogEntry {
+    pub guard_id: usize,
+    pub event: ShiftState,
+    pub timestamp: Timestamp,
+}
+
+pub struct Guard {
+    pub guard_id: usize,
+    pub minutes_slept: u32,
+    pub minutes_slept_by_minute: [u32; 60],
+    pub longest_nap: u32,
+}
+
+impl Guard {
+    /// The minute of the hour this guard was most often asleep, earliest on a tie,
+    /// and how many times.
+    pub fn sleepiest_minute(&self) -> (usize, u32) {
+        let mut sleepiest = (0, 0);
+        for (min, &sleeps) in self.minutes_slept_by_minute.iter().enumerate() {
+            if sleepiest.1 < sleeps {
+                sleepiest = (min, sleeps);
+            }
+        }
+        sleepiest
+    }
+
+    /// This guard together with their sleepiest minute.
+    pub fn choice(&self) -> GuardChoice {
+        let (minute, times_asleep) = self.sleepiest_minute();
+        GuardChoice { guard_id: self.guard_id, minute, times_asleep }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{:02}-{:02} {:02}:{:02}", self.year, self.month, self.day, self.hour, self.min)
+    }
+}
+
+/// Shown in the puzzle's own format, e.g. `[1518-11-01 00:00] Guard #10 begins shift`.
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.event {
+            ShiftState::Start => write!(f, "[{}] Guard #{} begins shift", self.timestamp, self.guard_id),
+            ShiftState::Awake => write!(f, "[{}] wakes up", self.timestamp),
+            ShiftState::Asleep => write!(f, "[{}] falls asleep", self.timestamp),
+        }
+    }
+}
+
+impl fmt::Display for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let minutes_slept_by_minute: String = self.minutes_slept_by_minute.
+            iter().
+            map(|m| m.to_string()).collect::<Vec<String>>().join(":");
+        write!(f, "{}, {}, {}", self.guard_id, self.minutes_slept, minutes_slept_by_minute)
+    }
+}
+
+const TIMESTAMP_GRAMMAR: &str = "[YYYY-MM-DD hh:mm]";
+const EVENT_GRAMMAR: &str = "Guard #<id> begins shift, falls asleep or wakes up";
+const CSV_HEADER: &str = "timestamp,guard,event";
+const CSV_GRAMMAR: &str = "timestamp,guard,event, e.g. 1518-11-01 00:00,10,begins shift";
+
+impl LogEntry {
+    /// Parses `[YYYY-MM-DD hh:mm] <event>`, with any spacing between the parts.
+    pub fn parse(entry: &str) -> Result<LogEntry, ParseError> {
+        let line = entry.trim();
+        let close = match line.find(']') {
+            Some(close) if line.starts_with('[') => close,
+            _ => return Err(ParseError::at(entry, line, TIMESTAMP_GRAMMAR)),
+        };
+
+        let timestamp = parse_timestamp(entry, &line[1..close])?;
+
+        let event_text = line[close + 1..].trim();
+        let token = if event_text.is_empty() { &line[line.len()..] } else { event_text };
+        let (guard_id, event) = parse_event(entry, token, None)?;
+
+        Ok(LogEntry { guard_id, event, timestamp })
+    }
+
+    /// Parses a CSV row `timestamp,guard,event`, where the guard may be left empty for
+    /// falling asleep and waking up, and any field may be double quoted.
+    pub fn parse_csv(entry: &str) -> Result<LogEntry, ParseError> {
+        let fields: Vec<&str> = entry.split(',').
+            map(|field| field.trim().trim_matches('"').trim()).
+            collect();
+        if fields.len() != 3 {
+            return Err(ParseError::at(entry, entry.trim(), CSV_GRAMMAR));
+        }
+
+        let timestamp = parse_timestamp(entry, fields[0])?;
+        let guard = if fields[1].is_empty() { None } else { Some(parse_number(entry, fields[1], "a guard id")?) };
+        let token = if fields[2].is_empty() { &entry[entry.len()..] } else { fields[2] };
+        let (guard_id, event) = parse_event(entry, token, guard)?;
+
+        Ok(LogEntry { guard_id, event, timestamp })
+    }
+
+    /// Parses a JSON object such as `{"timestamp": "1518-11-01 00:00", "guard": 10,
+    /// "event": "begins shift"}`, where the guard is optional as in CSV.
+    pub fn parse_json(entry: &str) -> Result<LogEntry, ParseError> {
+        let object = Json::parse(entry)?;
+        let field = |key: &str| object.get(key).filter(|v| **v != Json::Null);
+        let whole = entry.trim();
+        // Where a string value sits in the line, for errors within it; escapes hide it.
+        let locate = |text: &str| entry.find(text).map_or(whole, |i| &entry[i..i + text.len()]);
+
+        let timestamp = match field("timestamp").and_then(Json::as_str) {
+            Some(text) => parse_timestamp(text, text).map_err(|e| e.within(entry, locate(text)))?,
+            None => return Err(ParseError::at(entry, whole, "a \"timestamp\" string")),
+        };
+        let guard = match field("guard") {
+            Some(guard) => Some(guard.as_u64().
+                ok_or_else(|| ParseError::at(entry, whole, "a numeric \"guard\" id"))? as usize),
+            None => None,
+        };
+        let (guard_id, event) = match field("event").and_then(Json::as_str) {
+            Some(text) => parse_event(text, text, guard).map_err(|e| e.within(entry, locate(text)))?,
+            None => return Err(ParseError::at(entry, whole, "an \"event\" string")),
+        };
+
+        Ok(LogEntry { guard_id, event, timestamp })
+    }
+
+    /// Parses a line in whichever format it is written: bracketed text, a JSON object or
+    /// a CSV row.
+    pub fn read(entry: &str) -> Result<LogEntry, ParseError> {
+        match entry.trim_start().chars().next() {
+            Some('[') => LogEntry::parse(entry),
+            Some('{') => LogEntry::parse_json(entry),
+            _ => LogEntry::parse_csv(entry),
+        }
+    }
+}
+
+/// Parses an event, `Guard #<id> begins shift`, `falls asleep` or `wakes up`; the guard
+/// may instead come from a separate field, as `begins shift` with `guard` given.
+fn parse_event(entry: &str, token: &str, guard: Option<usize>) -> Result<(usize, ShiftState), ParseError> {
+    let words: Vec<&str> = token.split_whitespace().collect();
+    match (words.as_slice(), guard) {
+        (["Guard", id, "begins", "shift"], _) if id.starts_with('#') =>
+            Ok((parse_number(entry, &id[1..], "a guard id")?, ShiftState::Start)),
+        (["begins", "shift"], Some(guard_id)) => Ok((guard_id, ShiftState::Start)),
+        (["begins", "shift"], None) => Err(ParseError::at(entry, token, "a guard id with begins shift")),
+        (["falls", "asleep"], _) => Ok((guard.unwrap_or(0), ShiftState::Asleep)),
+        (["wakes", "up"], _) => Ok((guard.unwrap_or(0), ShiftState::Awake)),
+        _ => Err(ParseError::at(entry, token, EVENT_GRAMMAR)),
+    }
+}
+
+/// Reads a log in any mix of the bracketed, CSV and JSON lines formats, in file order,
+/// pairing each entry with its line. CSV header rows and blank lines are skipped.
+fn read_lines(input: &str) -> Result<Vec<(&str, LogEntry)>, ParseError> {
+    let mut lines = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() || line.trim().eq_ignore_ascii_case(CSV_HEADER) {
+            continue;
+        }
+        lines.push((line, LogEntry::read(line).map_err(|e| e.offset_lines(i))?));
+    }
+    Ok(lines)
+}
+
+/// Reads a log in any mix of the bracketed, CSV and JSON lines formats, in file order.
+pub fn read_log(input: &str) -> Result<Vec<LogEntry>, ParseError> {
+    read_lines(input).map(|lines| lines.into_iter().map(|(_, entry)| entry).collect())
+}
+
+/// Writes entries back out in the puzzle's bracketed format, one per line.
+pub fn write_log<W: Write>(out: &mut W, entries: &[LogEntry]) -> io::Result<()> {
+    for entry in entries {
+        writeln!(out, "{}", entry)?;
+    }
+    Ok(())
+}
+
+/// Parses the `YYYY-MM-DD hh:mm` between the brackets of a log entry.
+fn parse_timestamp(entry: &str, text: &str) -> Result<Timestamp, ParseError> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    if parts.len() != 2 {
+        return Err(ParseError::at(entry, text, TIMESTAMP_GRAMMAR));
+    }
+
+    let date = parts[0];
+    let date_values: Vec<usize> = date.
+        split('-').
+        map(|t| parse_number(entry, t, "a date YYYY-MM-DD")).
+        collect::<Result<_, _>>()?;
+    if date_values.len() != 3 {
+        return Err(ParseError::at(entry, date, "a date YYYY-MM-DD"));
+    }
+
+    let (hour, min) = parse_pair(entry, parts[1], ':', "a time hh:mm")?;
+
+    let (year, month, day) = (date_values[0], date_values[1], date_values[2]);
+    if Timestamp::new(year, month, day, 0, 0).is_none() {
+        return Err(ParseError::at(entry, date, "a calendar date"));
+    }
+    Timestamp::new(year, month, day, hour, min).
+        ok_or_else(|| ParseError::at(entry, parts[1], "a time from 00:00 to 23:59"))
+}
+
+/// A stretch asleep, from the minute the guard fell asleep up to the minute they woke.
+#[derive(Copy,Clone,Debug,PartialEq,Eq)]
+pub struct Nap {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Nap {
+    pub fn minutes(&self) -> i64 {
+        self.start.minutes_until(&self.end)
+    }
+}
+
+/// One guard's shift: when it began and the naps taken before the next shift.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Shift {
+    pub guard_id: usize,
+    pub start: Timestamp,
+    pub naps: Vec<Nap>,
+}
+
+impl Shift {
+    /// The midnight the shift watches over: shifts starting in the afternoon or evening
+    /// count towards the next day.
+    pub fn date(&self) -> Timestamp {
+        let day = if self.start.hour >= 12 { self.start.add_minutes(MINUTES_PER_DAY) } else { self.start };
+        Timestamp { hour: 0, min: 0, ..day }
+    }
+
+    pub fn is_asleep(&self, at: &Timestamp) -> bool {
+        self.naps.iter().any(|nap| nap.start <= *at && *at < nap.end)
+    }
+
+    /// The minutes of the midnight hour over the shift's date, 00:00 to 00:59, that the
+    /// guard slept through. Time asleep outside that hour is not counted.
+    pub fn midnight_minutes(&self) -> impl Iterator<Item = usize> + '_ {
+        let date = self.date();
+        (0..60).filter(move |&min| self.is_asleep(&Timestamp { min, ..date }))
+    }
+}
+
+/// Why a sorted log could not be split into shifts: the offending entry, or the log's
+/// length when it ends mid-nap, and what was expected there.
+#[derive(Debug,PartialEq,Eq)]
+pub struct ShiftError {
+    pub entry: usize,
+    pub expected: &'static str,
+}
+
+/// Groups chronologically sorted entries into shifts, checking that every nap starts
+/// during a shift and ends with the guard waking before the shift is over. Naps and
+/// wakings name no guard (id 0) or else the guard on shift.
+pub fn group_shifts(entries: &[LogEntry]) -> Result<Vec<Shift>, ShiftError> {
+    let mut shifts: Vec<Shift> = vec![];
+    let mut asleep_since: Option<Timestamp> = None;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let on_shift = shifts.last().map(|shift| shift.guard_id);
+        let named_other = entry.guard_id != 0 && on_shift.is_some_and(|id| id != entry.guard_id);
+
+        match entry.event {
+            ShiftState::Start => {
+                if asleep_since.is_some() {
+                    return Err(ShiftError { entry: i, expected: "wakes up before the next shift begins" });
+                }
+                shifts.push(Shift { guard_id: entry.guard_id, start: entry.timestamp, naps: vec![] });
+            },
+            ShiftState::Asleep => {
+                if shifts.is_empty() {
+                    return Err(ShiftError { entry: i, expected: "Guard #<id> begins shift before anyone falls asleep" });
+                }
+                if named_other {
+                    return Err(ShiftError { entry: i, expected: "the guard on shift to fall asleep" });
+                }
+                if asleep_since.is_some() {
+                    return Err(ShiftError { entry: i, expected: "wakes up before falling asleep again" });
+                }
+                asleep_since = Some(entry.timestamp);
+            },
+            ShiftState::Awake => {
+                if named_other {
+                    return Err(ShiftError { entry: i, expected: "the guard on shift to wake up" });
+                }
+                let start = asleep_since.take().
+                    ok_or(ShiftError { entry: i, expected: "falls asleep before wakes up" })?;
+                shifts.last_mut().unwrap().naps.push(Nap { start, end: entry.timestamp });
+            },
+        }
+    }
+
+    if asleep_since.is_some() {
+        return Err(ShiftError { entry: entries.len(), expected: "wakes up before the log ends" });
+    }
+    Ok(shifts)
+}
+
+/// A guard paired with the minute they were most often asleep.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct GuardChoice {
+    pub guard_id: usize,
+    pub minute: usize,
+    pub times_asleep: u32,
+}
+
+impl fmt::Display for GuardChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "guard_id: {}, min: {}", self.guard_id, self.times_asleep)?;
+        write!(f, "{}", self.guard_id * self.minute)
+    }
+}
+
+impl Answer for GuardChoice {
+    fn value(&self) -> Json {
+        Json::from(self.guard_id * self.minute)
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        vec![
+            ("guard_id", Json::from(self.guard_id)),
+            ("minute", Json::from(self.minute)),
+            ("times_asleep", Json::from(self.times_asleep)),
+        ]
+    }
+}
+
+/// Sleep totals for every guard on duty in a log of shifts.
+pub struct GuardStats {
+    pub guards: BTreeMap<usize, Guard>,
+    pub shifts: Vec<Shift>,
+}
+
+impl GuardStats {
+    pub fn new(shifts: &[Shift]) -> GuardStats {
+        let mut guards = BTreeMap::new();
+        for shift in shifts {
+            let guard = guards.entry(shift.guard_id).
+                or_insert(Guard {
+                    guard_id: shift.guard_id,
+                    minutes_slept: 0,
+                    minutes_slept_by_minute: [0; 60],
+                    longest_nap: 0,
+                });
+
+            for nap in &shift.naps {
+                let minutes = nap.minutes() as u32;
+                guard.minutes_slept += minutes;
+                guard.longest_nap = guard.longest_nap.max(minutes);
+            }
+            for min in shift.midnight_minutes() {
+                guard.minutes_slept_by_minute[min] += 1;
+            }
+        }
+
+        GuardStats { guards, shifts: shifts.to_vec() }
+    }
+
+    pub fn choose(&self, strategy: &dyn Strategy) -> Option<GuardChoice> {
+        strategy.choose(self)
+    }
+
+    /// How many guards were asleep at each minute of the midnight hour, by shift date.
+    pub fn by_date(&self) -> BTreeMap<Timestamp, [u32; 60]> {
+        let mut histograms = BTreeMap::new();
+        for shift in &self.shifts {
+            let date = shift.date();
+            let histogram = histograms.entry(date).or_insert([0; 60]);
+            for min in shift.midnight_minutes() {
+                histogram[min] += 1;
+            }
+        }
+        histograms
+    }
+
+    /// The midnight hour of each shift, asleep as `#` and awake as `.`, as in the puzzle.
+    pub fn timeline(&self) -> String {
+        let mut lines = vec![
+            "Date   ID     Minute".to_string(),
+            format!("{:14}{}", "", (0..60).map(|m| (b'0' + m / 10) as char).collect::<String>()),
+            format!("{:14}{}", "", (0..60).map(|m| (b'0' + m % 10) as char).collect::<String>()),
+        ];
+
+        for shift in &self.shifts {
+            let date = shift.date();
+            let mut minutes = vec!['.'; 60];
+            for min in shift.midnight_minutes() {
+                minutes[min] = '#';
+            }
+            let minutes: String = minutes.into_iter().collect();
+            lines.push(format!("{:02}-{:02}  {:<6} {}", date.month, date.day, format!("#{}", shift.guard_id), minutes));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// A way of picking the guard to sneak past.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// The chosen guard and minute, or `None` when nobody ever slept.
+    fn choose(&self, stats: &GuardStats) -> Option<GuardChoice>;
+}
+
+/// Picks the guard with the most minutes asleep, at their sleepiest minute.
+pub struct MostAsleep;
+
+impl Strategy for MostAsleep {
+    fn name(&self) -> &'static str {
+        "most-asleep"
+    }
+
+    fn choose(&self, stats: &GuardStats) -> Option<GuardChoice> {
+        stats.guards.values().
+            filter(|g| g.minutes_slept > 0).
+            max_by_key(|g| (g.minutes_slept, Reverse(g.guard_id))).
+            map(Guard::choice)
+    }
+}
+
+/// Picks the guard most frequently asleep on the same minute.
+pub struct MostConsistentMinute;
+
+impl Strategy for MostConsistentMinute {
+    fn name(&self) -> &'static str {
+        "most-consistent"
+    }
+
+    fn choose(&self, stats: &GuardStats) -> Option<GuardChoice> {
+        stats.guards.values().
+            map(Guard::choice).
+            filter(|c| c.times_asleep > 0).
+            max_by_key(|c| (c.times_asleep, Reverse(c.guard_id)))
+    }
+}
+
+/// Picks the guard who took the longest single nap, at their sleepiest minute.
+pub struct LongestNap;
+
+impl Strategy for LongestNap {
+    fn name(&self) -> &'static str {
+        "longest-nap"
+    }
+
+    fn choose(&self, stats: &GuardStats) -> Option<GuardChoice> {
+        stats.guards.values().
+            filter(|g| g.longest_nap > 0).
+            max_by_key(|g| (g.longest_nap, Reverse(g.guard_id))).
+            map(Guard::choice)
+    }
+}
+
+pub fn strategy_by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name {
+        "most-asleep" => Box::new(MostAsleep),
+        "most-consistent" => Box::new(MostConsistentMinute),
+        "longest-nap" => Box::new(LongestNap),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// The guard a strategy chose, with the shift timeline when it was asked for.
+pub struct StrategyChoice {
+    pub strategy: &'static str,
+    /// The guard the strategy picked, unless nobody ever fell asleep.
+    pub choice: Option<GuardChoice>,
+    pub timeline: Option<String>,
+}
+
+impl fmt::Display for StrategyChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref timeline) = self.timeline {
+            writeln!(f, "{}\n", timeline)?;
+        }
+        match self.choice {
+            Some(ref choice) => write!(f, "{}", choice),
+            None => write!(f, "No guard ever fell asleep"),
+        }
+    }
+}
+
+impl Answer for StrategyChoice {
+    fn value(&self) -> Json {
+        self.choice.as_ref().map_or(Json::Null, |choice| choice.value())
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let mut extras = vec![("strategy", Json::from(self.strategy))];
+        if let Some(ref choice) = self.choice {
+            extras.extend(choice.extras());
+        }
+        extras
+    }
+}
+
+pub struct Day4 {
+    pub part1_strategy: Box<dyn Strategy>,
+    pub part2_strategy: Box<dyn Strategy>,
+    /// Whether to print the shifts' timeline with each answer.
+    pub timeline: bool,
+}
+
+impl Default for Day4 {
+    fn default() -> Day4 {
+        Day4 {
+            part1_strategy: Box::new(MostAsleep),
+            part2_strategy: Box::new(MostConsistentMinute),
+            timeline: false,
+        }
+    }
+}
+
+impl Day4 {
+    fn solve(&self, shifts: &[Shift], strategy: &dyn Strategy) -> StrategyChoice {
+        let stats = GuardStats::new(shifts);
+        StrategyChoice {
+            strategy: strategy.name(),
+            choice: stats.choose(strategy),
+            timeline: if self.timeline { Some(stats.timeline()) } else { None },
+        }
+    }
+}
+
+impl Solver for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<Shift>;
+    type Part1 = StrategyChoice;
+    type Part2 = StrategyChoice;
+
+    /// Parses the log in any of the formats `read_log` accepts, sorts it by timestamp
+    /// and groups it into shifts.
+    fn parse(&self, input: &str) -> Result<Vec<Shift>, ParseError> {
+        // Keep each entry's line, to point any error in the shifts back at it
+        let mut lines = read_lines(input)?;
+
+        // Sort by timestamp, keeping the log's order for entries at the same minute
+        lines.sort_by_key(|(_, entry)| entry.timestamp);
+
+        let (sources, entries): (Vec<&str>, Vec<LogEntry>) = lines.into_iter().unzip();
+        group_shifts(&entries).map_err(|e| match sources.get(e.entry) {
+            Some(line) => ParseError::at(input, line.trim(), e.expected),
+            None => ParseError::at(input, &input[input.len()..], e.expected),
+        })
+    }
+
+    fn part1(&self, shifts: &Vec<Shift>) -> StrategyChoice {
+        self.solve(shifts, self.part1_strategy.as_ref())
+    }
+
+    fn part2(&self, shifts: &Vec<Shift>) -> StrategyChoice {
+        self.solve(shifts, self.part2_strategy.as_ref())
+    }
+
+    /// Takes `timeline` to print the shifts, and strategy names for part 1 then part 2.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        let mut strategies = vec![];
+        for param in params {
+            if param == "timeline" {
+                self.timeline = true;
+            } else {
+                strategies.push(strategy_by_name(param).ok_or_else(|| format!(
+                    "Invalid strategy: {} (expected most-asleep, most-consistent or longest-nap)", param))?);
+            }
+        }
+
+        let mut strategies = strategies.into_iter();
+        if let Some(strategy) = strategies.next() {
+            self.part1_strategy = strategy;
+        }
+        if let Some(strategy) = strategies.next() {
+            self.part2_strategy = strategy;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_log_gives_the_same_answers() {
+        let day4 = Day4::default();
+        let mixed = include_str!("../test3.dat");
+
+        let mut bracketed = vec![];
+        write_log(&mut bracketed, &read_log(mixed).unwrap()).unwrap();
+        let bracketed = String::from_utf8(bracketed).unwrap();
+        assert!(bracketed.lines().all(|line| line.starts_with('[')));
+
+        let (original, written) = (day4.parse(mixed).unwrap(), day4.parse(&bracketed).unwrap());
+        assert_eq!(original, written);
+        assert_eq!(day4.part1(&original).value(), day4.part1(&written).value());
+        assert_eq!(day4.part2(&original).value(), day4.part2(&written).value());
+    }
+
+    #[test]
+    fn nap_naming_another_guard_is_an_error() {
+        let log = "timestamp,guard,event\n\
+                   1518-11-01 00:00,10,begins shift\n\
+                   1518-11-01 00:05,42,falls asleep\n\
+                   1518-11-01 00:10,,wakes up\n";
+        let error = Day4::default().parse(log).unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let entries = read_log(log).unwrap();
+        assert_eq!(group_shifts(&entries).unwrap_err(),
+                   ShiftError { entry: 1, expected: "the guard on shift to fall asleep" });
+    }
+
+    fn stats(log: &str) -> GuardStats {
+        GuardStats::new(&Day4::default().parse(log).unwrap())
+    }
+
+    fn date(month: usize, day: usize) -> Timestamp {
+        Timestamp::new(1518, month, day, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn histograms_by_date_follow_the_timeline() {
+        let stats = stats(include_str!("../test.dat"));
+        let by_date = stats.by_date();
+        assert_eq!(by_date.keys().cloned().collect::<Vec<_>>(),
+                   (1..=5).map(|day| date(11, day)).collect::<Vec<_>>());
+
+        let first = &by_date[&date(11, 1)];
+        let asleep: Vec<usize> = (0..60).filter(|&min| first[min] == 1).collect();
+        assert_eq!(asleep, (5..25).chain(30..55).collect::<Vec<_>>());
+        assert!(first.iter().all(|&count| count <= 1));
+
+        // Each timeline row after the header marks the same minutes as its date's histogram.
+        for (row, histogram) in stats.timeline().lines().skip(3).zip(by_date.values()) {
+            let marks: Vec<bool> = row.chars().skip(14).map(|c| c == '#').collect();
+            assert_eq!(marks, histogram.iter().map(|&count| count > 0).collect::<Vec<_>>(), "{}", row);
+        }
+
+        // Summed over dates, the histograms give every guard's minutes together.
+        for min in 0..60 {
+            let by_guard: u32 = stats.guards.values().map(|g| g.minutes_slept_by_minute[min]).sum();
+            assert_eq!(by_date.values().map(|h| h[min]).sum::<u32>(), by_guard);
+        }
+    }
+
+    #[test]
+    fn histograms_by_date_add_up_shifts_on_the_same_date() {
+        let stats = stats("[1518-03-01 23:58] Guard #1 begins shift\n\
+                           [1518-03-02 00:10] falls asleep\n\
+                           [1518-03-02 00:20] wakes up\n\
+                           [1518-03-02 00:21] Guard #2 begins shift\n\
+                           [1518-03-02 00:30] falls asleep\n\
+                           [1518-03-02 00:40] wakes up\n");
+        let by_date = stats.by_date();
+        assert_eq!(by_date.len(), 1);
+
+        let histogram = &by_date[&date(3, 2)];
+        let asleep: Vec<usize> = (0..60).filter(|&min| histogram[min] == 1).collect();
+        assert_eq!(asleep, (10..20).chain(30..40).collect::<Vec<_>>());
+        assert_eq!(histogram.iter().sum::<u32>(), 20);
+    }
+
+}
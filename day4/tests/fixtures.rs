@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day4;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day4::Day4::default, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,371 @@
+extern crate common;
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read};
+use std::thread;
+
+use common::{Solver, ParseError};
+
+/// What happens when one unit arrives next to another.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Reaction {
+    Inert,
+    Annihilate,
+    /// Both units are replaced by this one, which may react in turn.
+    Replace(u8),
+}
+
+const RULE_GRAMMAR: &str = "<unit><unit>, or <unit><unit> -> <unit>";
+
+/// Which ordered pairs of units react, indexed by the pair's two bytes.
+#[derive(Clone)]
+pub struct Rules {
+    table: Vec<Reaction>,
+}
+
+impl Rules {
+    pub fn inert() -> Rules {
+        Rules { table: vec![Reaction::Inert; 256 * 256] }
+    }
+
+    /// The puzzle's rule: the same letter in opposite cases annihilates.
+    pub fn polarity() -> Rules {
+        let mut rules = Rules::inert();
+        for lower in b'a'..=b'z' {
+            let upper = lower.to_ascii_uppercase();
+            rules.set(lower, upper, Reaction::Annihilate);
+            rules.set(upper, lower, Reaction::Annihilate);
+        }
+        rules
+    }
+
+    /// Parses one rule per line: `xy` for a pair that annihilates, `xy -> z` for one
+    /// replaced by `z`. Units are single ASCII characters; lines starting `#` are comments.
+    pub fn parse(text: &str) -> Result<Rules, ParseError> {
+        let mut rules = Rules::inert();
+        for (i, line) in text.lines().enumerate() {
+            let rule = line.trim();
+            if rule.is_empty() || rule.starts_with('#') {
+                continue;
+            }
+            rules.parse_rule(line, rule).map_err(|e| e.offset_lines(i))?;
+        }
+        Ok(rules)
+    }
+
+    fn parse_rule(&mut self, line: &str, rule: &str) -> Result<(), ParseError> {
+        let mut sides = rule.splitn(2, "->");
+        let pair = sides.next().unwrap().trim();
+        let units = pair.as_bytes();
+        if units.len() != 2 || !units.iter().all(u8::is_ascii_graphic) {
+            return Err(ParseError::at(line, pair, RULE_GRAMMAR));
+        }
+
+        let reaction = match sides.next().map(str::trim) {
+            None => Reaction::Annihilate,
+            Some(product) if product.len() == 1 && product.as_bytes()[0].is_ascii_graphic() =>
+                Reaction::Replace(product.as_bytes()[0]),
+            Some(product) => return Err(ParseError::at(line, product, "a single replacement unit")),
+        };
+        self.set(units[0], units[1], reaction);
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Rules, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Rules::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn set(&mut self, left: u8, right: u8, reaction: Reaction) {
+        self.table[usize::from(left) * 256 + usize::from(right)] = reaction;
+    }
+
+    pub fn react(&self, left: u8, right: u8) -> Reaction {
+        self.table[usize::from(left) * 256 + usize::from(right)]
+    }
+
+    /// Adds `unit` to the right of the reduced units on `stack`, reacting as it goes.
+    pub fn push(&self, stack: &mut Vec<u8>, unit: u8) {
+        let mut unit = unit;
+        while let Some(&top) = stack.last() {
+            match self.react(top, unit) {
+                Reaction::Inert => break,
+                Reaction::Annihilate => {
+                    stack.pop();
+                    return;
+                },
+                Reaction::Replace(product) => {
+                    stack.pop();
+                    unit = product;
+                },
+            }
+        }
+        stack.push(unit);
+    }
+
+    /// Reduces the polymer in a single pass, keeping the surviving units on a stack and
+    /// letting each new unit react with the one on top. Reactions happen as units
+    /// arrive, so for rules whose outcome depends on the order this is one of several.
+    pub fn reduce(&self, polymer: &[u8]) -> Vec<u8> {
+        let mut reduced = Polymer::new(self);
+        reduced.extend(polymer);
+        reduced.into_units()
+    }
+}
+
+/// A polymer reduced as it is read: only the surviving units are kept, so a polymer
+/// streamed in chunks needs memory for its reduced form rather than the whole input.
+/// This is for library callers; the command line runner reads every day's input whole,
+/// so `advent2018 day5` still holds the full polymer in memory.
+pub struct Polymer<'a> {
+    rules: &'a Rules,
+    units: Vec<u8>,
+    consumed: u64,
+}
+
+impl<'a> Polymer<'a> {
+    pub fn new(rules: &'a Rules) -> Polymer<'a> {
+        Polymer { rules, units: vec![], consumed: 0 }
+    }
+
+    /// Appends the next chunk of units and reduces it against what came before. ASCII
+    /// whitespace is skipped, so line breaks in a streamed polymer are harmless.
+    pub fn extend(&mut self, chunk: &[u8]) {
+        for &unit in chunk.iter().filter(|u| !u.is_ascii_whitespace()) {
+            self.rules.push(&mut self.units, unit);
+        }
+        self.consumed += chunk.len() as u64;
+    }
+
+    /// Streams everything from `reader` through `extend`, a buffer at a time.
+    pub fn read_from<R: Read>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self.extend(&buffer[..n]),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Length of the polymer reduced so far.
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Bytes passed to `extend` so far, whitespace included.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    pub fn units(&self) -> &[u8] {
+        &self.units
+    }
+
+    pub fn into_units(self) -> Vec<u8> {
+        self.units
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules::polarity()
+    }
+}
+
+pub fn reduce_bytes(polymer: &[u8]) -> Vec<u8> {
+    Rules::polarity().reduce(polymer)
+}
+
+pub fn reduce(s: &str) -> String {
+    // Only ASCII letters react, so multi-byte characters survive whole
+    String::from_utf8(reduce_bytes(s.as_bytes())).unwrap()
+}
+
+/// Length of the polymer once `unit`, in either case, is removed and the rest reduced.
+pub fn reduced_len_without(rules: &Rules, polymer: &[u8], unit: u8) -> usize {
+    let unit = unit.to_ascii_lowercase();
+    let mut stack = Vec::with_capacity(polymer.len());
+    for &u in polymer.iter().filter(|u| u.to_ascii_lowercase() != unit) {
+        rules.push(&mut stack, u);
+    }
+    stack.len()
+}
+
+/// Length of the shortest polymer produced by removing one unit type before reducing.
+///
+/// Under the polarity rule, removing a unit never lets units react that were kept apart,
+/// so each removal can start from the already reduced polymer; other rules start from
+/// the original. The unit types are shared out over `threads`.
+pub fn shortest_reduction(rules: &Rules, polymer: &str, threads: usize) -> usize {
+    let mut units: Vec<u8> = polymer.bytes().map(|u| u.to_ascii_lowercase()).collect();
+    units.sort_unstable();
+    units.dedup();
+    if units.is_empty() {
+        return 0;
+    }
+
+    let start = if rules.table == Rules::polarity().table {
+        rules.reduce(polymer.as_bytes())
+    } else {
+        polymer.as_bytes().to_vec()
+    };
+    let start = &start;
+    let chunk_len = units.len().div_ceil(threads.max(1));
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = units.chunks(chunk_len).
+            map(|chunk| scope.spawn(move || {
+                chunk.iter().map(|&unit| reduced_len_without(rules, start, unit)).min()
+            })).
+            collect();
+        workers.into_iter().
+            filter_map(|worker| worker.join().unwrap()).
+            min().
+            unwrap()
+    })
+}
+
+pub struct Day5 {
+    /// Threads to share the part 2 unit removals between.
+    pub threads: usize,
+    /// Reactions loaded from a rule file, in place of the polarity rule.
+    pub rules: Option<Rules>,
+}
+
+impl Default for Day5 {
+    fn default() -> Day5 {
+        Day5{threads: thread::available_parallelism().map_or(1, |n| n.get()), rules: None}
+    }
+}
+
+impl Day5 {
+    fn rules(&self) -> Cow<'_, Rules> {
+        match self.rules {
+            Some(ref rules) => Cow::Borrowed(rules),
+            None => Cow::Owned(Rules::polarity()),
+        }
+    }
+}
+
+impl Solver for Day5 {
+    const DAY: u32 = 5;
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    /// Parses a polymer of letters, or of any printable ASCII units under custom rules.
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        let polymer = input.trim_end();
+        let (is_unit, expected): (fn(&char) -> bool, _) = match self.rules {
+            Some(_) => (char::is_ascii_graphic, "a polymer of printable ASCII units"),
+            None => (char::is_ascii_alphabetic, "a polymer of unit letters"),
+        };
+        match polymer.find(|c: char| !is_unit(&c)) {
+            Some(i) => Err(ParseError::at(input, &polymer[i..], expected)),
+            None => Ok(polymer.to_string()),
+        }
+    }
+
+    fn part1(&self, polymer: &String) -> usize {
+        let rules = self.rules();
+        let mut reduced = Polymer::new(&rules);
+        reduced.extend(polymer.as_bytes());
+        reduced.len()
+    }
+
+    fn part2(&self, polymer: &String) -> usize {
+        shortest_reduction(&self.rules(), polymer, self.threads)
+    }
+
+    /// Takes a number as the thread count for part 2, and anything else as a rule file.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        for param in params {
+            match param.parse() {
+                Ok(threads) => self.threads = threads,
+                Err(_) => self.rules = Some(Rules::load(param)?),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    /// Removes the first reacting pair until none is left, the way the puzzle describes.
+    fn reduce_naively(polymer: &[u8]) -> Vec<u8> {
+        let mut units = polymer.to_vec();
+        while let Some(i) = units.windows(2).position(|w| w[0] != w[1] && w[0].eq_ignore_ascii_case(&w[1])) {
+            units.drain(i..i + 2);
+        }
+        units
+    }
+
+    fn chunked(rules: &Rules, polymer: &[u8], size: usize) -> Vec<u8> {
+        let mut reduced = Polymer::new(rules);
+        for chunk in polymer.chunks(size) {
+            reduced.extend(chunk);
+        }
+        assert_eq!(reduced.consumed(), polymer.len() as u64);
+        reduced.into_units()
+    }
+
+    #[test]
+    fn chunked_extend_matches_a_whole_reduction() {
+        let rules = Rules::polarity();
+        let mut rng = Rng::new(11);
+        let polymer: Vec<u8> = (0..2000).map(|_| b"aAbBcC"[rng.below(6) as usize]).collect();
+
+        let whole = rules.reduce(&polymer);
+        assert_eq!(whole, reduce_naively(&polymer));
+        for &size in &[1, 2, 3, 7, 64, 1999, 4096] {
+            assert_eq!(chunked(&rules, &polymer, size), whole, "chunks of {}", size);
+        }
+    }
+
+    #[test]
+    fn pair_split_across_chunks_reacts() {
+        let rules = Rules::polarity();
+        let mut reduced = Polymer::new(&rules);
+        reduced.extend(b"dabA");
+        reduced.extend(b"\n");
+        reduced.extend(b"aBc");
+        assert_eq!(reduced.units(), b"dac");
+        assert_eq!(reduced.consumed(), 8);
+
+        let example = b"dabAcCaCBAcCcaDA";
+        assert_eq!(chunked(&rules, example, 5), b"dabCBAcaDA".to_vec());
+    }
+
+    #[test]
+    fn custom_rules_stream_like_a_whole_reduction() {
+        let rules = Rules::load(concat!(env!("CARGO_MANIFEST_DIR"), "/replace.rules")).unwrap();
+        let polymer = b"ab(ab)[ababc]{ba}ccab";
+        let whole = rules.reduce(polymer);
+        for size in 1..polymer.len() {
+            assert_eq!(chunked(&rules, polymer, size), whole, "chunks of {}", size);
+        }
+    }
+
+    #[test]
+    fn read_from_streams_every_buffer() {
+        let rules = Rules::polarity();
+        let polymer: Vec<u8> = b"aBbA".iter().cycle().take(200_000).cloned().chain(b"xYz\n".iter().cloned()).collect();
+
+        let mut reduced = Polymer::new(&rules);
+        reduced.read_from(&polymer[..]).unwrap();
+        assert_eq!(reduced.units(), b"xYz");
+        assert_eq!(reduced.consumed(), polymer.len() as u64);
+    }
+}
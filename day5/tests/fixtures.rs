@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day5;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day5::Day5::default, env!("CARGO_MANIFEST_DIR"));
+}
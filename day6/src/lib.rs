@@ -0,0 +1,776 @@
+extern crate common;
+extern crate grid;
+
+use std::fmt;
+use std::collections::VecDeque;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::{parse_lines, parse_pair};
+use grid::{Coord, Grid, Point};
+
+/// Parses a coordinate `x, y`, optionally wrapped in parentheses.
+pub fn parse_point(s: &str) -> Result<Point, ParseError> {
+    let coords = s.trim().trim_matches(|p| p == '(' || p == ')' );
+    let (x, y) = parse_pair(s, coords, ',', "a coordinate <x>, <y>")?;
+
+    Ok(Point::new(x, y))
+}
+
+/// The finite region with the largest area, unless every region is infinite.
+pub struct LargestArea {
+    pub region: Option<Region>,
+    pub metric: Metric,
+}
+
+impl fmt::Display for LargestArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.region {
+            Some(ref region) => write!(f, "({}): {}", region.point, region.area),
+            None => write!(f, "No finite region"),
+        }
+    }
+}
+
+impl Answer for LargestArea {
+    fn value(&self) -> Json {
+        Json::from(self.region.as_ref().map(|region| region.area))
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        let mut extras = vec![("metric", Json::from(self.metric.name()))];
+        if let Some(ref region) = self.region {
+            extras.push(("x", Json::from(region.point.x)));
+            extras.push(("y", Json::from(region.point.y)));
+        }
+        extras
+    }
+}
+
+/// How far apart two points are when deciding which one is nearest.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+    Euclidean,
+}
+
+impl Metric {
+    pub fn by_name(name: &str) -> Option<Metric> {
+        match name {
+            "manhattan" => Some(Metric::Manhattan),
+            "chebyshev" => Some(Metric::Chebyshev),
+            "euclidean" => Some(Metric::Euclidean),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Manhattan => "manhattan",
+            Metric::Chebyshev => "chebyshev",
+            Metric::Euclidean => "euclidean",
+        }
+    }
+
+    /// The distance between `a` and `b`; squared for `Euclidean`, which keeps it integral
+    /// without changing which point is nearest.
+    pub fn distance(self, a: Point, b: Point) -> u64 {
+        match self {
+            Metric::Manhattan => a.manhattan_distance(b),
+            Metric::Chebyshev => a.chebyshev_distance(b),
+            Metric::Euclidean => a.squared_distance(b),
+        }
+    }
+}
+
+/// An inclusive rectangle of locations.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The smallest bounds containing every one of `points`.
+    pub fn of(points: &[Point]) -> Bounds {
+        let mut bounds = Bounds{min: points[0], max: points[0]};
+        for &p in points {
+            bounds.include(p);
+        }
+        bounds
+    }
+
+    fn include(&mut self, p: Point) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn on_edge(&self, p: Point) -> bool {
+        p.x == self.min.x || p.x == self.max.x || p.y == self.min.y || p.y == self.max.y
+    }
+
+    /// Whether `p` lies strictly inside, off the edge.
+    pub fn surrounds(&self, p: Point) -> bool {
+        self.min.x < p.x && p.x < self.max.x && self.min.y < p.y && p.y < self.max.y
+    }
+
+    fn coord(&self, p: Point) -> Coord {
+        Coord::new((p.y - self.min.y) as usize, (p.x - self.min.x) as usize)
+    }
+
+    fn point(&self, c: Coord) -> Point {
+        Point::new(self.min.x + c.col as i64, self.min.y + c.row as i64)
+    }
+}
+
+/// Which point a location is nearest to.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Owner {
+    Unclaimed,
+    Point(usize),
+    Tie,
+}
+
+impl Owner {
+    fn merge(self, other: Owner) -> Owner {
+        match (self, other) {
+            (Owner::Unclaimed, o) | (o, Owner::Unclaimed) => o,
+            (Owner::Point(a), Owner::Point(b)) if a == b => Owner::Point(a),
+            _ => Owner::Tie,
+        }
+    }
+}
+
+/// The locations nearest to one point.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Region {
+    pub point: Point,
+    pub area: u64,
+    /// Whether the region carries on without end; its area then only counts the locations
+    /// within the bounds.
+    pub infinite: bool,
+}
+
+/// Every location within bounds large enough to hold each finite region whole, assigned to
+/// its nearest point.
+pub struct Regions {
+    pub metric: Metric,
+    pub bounds: Bounds,
+    /// One region per input point, in input order.
+    pub regions: Vec<Region>,
+    /// Locations equally near to more than one point.
+    pub ties: Vec<Point>,
+    owners: Grid<Owner>,
+}
+
+impl Regions {
+    /// Partitions the locations around `points` among them under `metric`.
+    ///
+    /// The grid metrics grow all regions at once with a breadth-first search, four-way for
+    /// `Manhattan` and eight-way for `Chebyshev`; a location's nearest points are then
+    /// exactly those of the neighbours it was reached from. `Euclidean` distances don't
+    /// follow grid paths, so each location searches outward through the points sorted by x.
+    ///
+    /// A region is infinite iff it touches the boundary of the points' hull:
+    ///
+    /// * `Manhattan`: the hull is the bounding box. Stepping away from a location on its
+    ///   edge moves one further from every point, so the owner keeps the whole ray beyond;
+    ///   and a location outside has the same owner as its nearest location on the edge.
+    /// * `Chebyshev`: the same argument for the bounding box of the points turned 45
+    ///   degrees, `(x + y, x - y)`, in which the metric is half the Manhattan distance.
+    ///   Locations only sit on every other diagonal there, so the bounds take in a margin
+    ///   of one beyond that box.
+    /// * `Euclidean`: the hull is the convex hull, and a point's cell is unbounded iff the
+    ///   point lies on its boundary. Finite cells can stick out past the bounding box, so
+    ///   the bounds also take in the corners of those cells.
+    ///
+    /// A point sharing its location with another owns nothing, and so is never infinite.
+    pub fn new(points: &[Point], metric: Metric) -> Regions {
+        let mut regions: Vec<Region> = points.iter().
+            map(|&point| Region{point, area: 0, infinite: false}).
+            collect();
+
+        let (bounds, owners) = match metric {
+            Metric::Manhattan => {
+                let bounds = Bounds::of(points);
+                (bounds, grow(points, &bounds, false))
+            }
+            Metric::Chebyshev => {
+                let bounds = diagonal_bounds(points);
+                (bounds, grow(points, &bounds, true))
+            }
+            Metric::Euclidean => {
+                let on_hull = on_convex_hull(points);
+                for (region, &on_hull) in regions.iter_mut().zip(&on_hull) {
+                    region.infinite = on_hull;
+                }
+                let bounds = voronoi_bounds(points, &on_hull);
+                (bounds, nearest_euclidean(points, &bounds))
+            }
+        };
+
+        let diagonal = Bounds::of(&points.iter().map(|&p| turn(p)).collect::<Vec<_>>());
+        let mut ties = vec![];
+        for (coord, owner) in owners.iter() {
+            let p = bounds.point(coord);
+            match *owner {
+                Owner::Point(i) => {
+                    regions[i].area += 1;
+                    match metric {
+                        Metric::Manhattan => regions[i].infinite |= bounds.on_edge(p),
+                        Metric::Chebyshev => regions[i].infinite |= !diagonal.surrounds(turn(p)),
+                        Metric::Euclidean => {}
+                    }
+                }
+                Owner::Tie => ties.push(p),
+                Owner::Unclaimed => {}
+            }
+        }
+        for (i, region) in regions.iter_mut().enumerate() {
+            if owners[bounds.coord(region.point)] != Owner::Point(i) {
+                region.infinite = false;
+            }
+        }
+
+        Regions{metric, bounds, regions, ties, owners}
+    }
+
+    /// Who owns location `p`, if it lies within the bounds.
+    pub fn owner(&self, p: Point) -> Option<Owner> {
+        if self.bounds.contains(p) {
+            self.owners.get(self.bounds.coord(p)).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// The finite region with the largest area, the earliest point winning a tie.
+    pub fn largest_finite(&self) -> Option<&Region> {
+        self.regions.iter().
+            filter(|r| !r.infinite).
+            fold(None, |best: Option<&Region>, r| match best {
+                Some(b) if b.area >= r.area => Some(b),
+                _ => Some(r),
+            })
+    }
+}
+
+/// Multi-source breadth-first search over `bounds`, merging owners of equally near locations.
+fn grow(points: &[Point], bounds: &Bounds, diagonal: bool) -> Grid<Owner> {
+    let mut owners = Grid::new(bounds.width(), bounds.height(), Owner::Unclaimed);
+    let mut distances = Grid::new(bounds.width(), bounds.height(), None);
+    let mut queue = VecDeque::new();
+
+    for (i, &p) in points.iter().enumerate() {
+        let c = bounds.coord(p);
+        if distances[c].is_none() {
+            distances[c] = Some(0);
+            queue.push_back(c);
+        }
+        owners[c] = owners[c].merge(Owner::Point(i));
+    }
+
+    while let Some(c) = queue.pop_front() {
+        let next = distances[c].unwrap() + 1;
+        let owner = owners[c];
+        let neighbours: Vec<Coord> = if diagonal {
+            owners.neighbours8(c).collect()
+        } else {
+            owners.neighbours(c).collect()
+        };
+        for n in neighbours {
+            match distances[n] {
+                None => {
+                    distances[n] = Some(next);
+                    owners[n] = owner;
+                    queue.push_back(n);
+                }
+                Some(d) if d == next => owners[n] = owners[n].merge(owner),
+                _ => {}
+            }
+        }
+    }
+
+    owners
+}
+
+fn nearest_euclidean(points: &[Point], bounds: &Bounds) -> Grid<Owner> {
+    let mut by_x: Vec<usize> = (0..points.len()).collect();
+    by_x.sort_by_key(|&i| points[i].x);
+
+    let mut owners = Grid::new(bounds.width(), bounds.height(), Owner::Unclaimed);
+    for y in bounds.min.y..=bounds.max.y {
+        for x in bounds.min.x..=bounds.max.x {
+            let q = Point::new(x, y);
+            let start = by_x.partition_point(|&i| points[i].x < x);
+
+            let mut best = u64::MAX;
+            let mut owner = Owner::Unclaimed;
+            let mut consider = |i: usize| {
+                let dx = points[i].x.abs_diff(x);
+                if dx * dx > best {
+                    return false;
+                }
+                let d = points[i].squared_distance(q);
+                if d < best {
+                    best = d;
+                    owner = Owner::Point(i);
+                } else if d == best {
+                    owner = Owner::Tie;
+                }
+                true
+            };
+            for &i in &by_x[start..] {
+                if !consider(i) {
+                    break;
+                }
+            }
+            for &i in by_x[..start].iter().rev() {
+                if !consider(i) {
+                    break;
+                }
+            }
+
+            owners[bounds.coord(q)] = owner;
+        }
+    }
+
+    owners
+}
+
+/// `p` turned 45 degrees and scaled, taking Chebyshev distances to twice Manhattan ones.
+fn turn(p: Point) -> Point {
+    Point::new(p.x + p.y, p.x - p.y)
+}
+
+/// Bounds taking in every location whose turned coordinates lie within one of the turned
+/// points' bounding box.
+fn diagonal_bounds(points: &[Point]) -> Bounds {
+    let turned: Vec<Point> = points.iter().map(|&p| turn(p)).collect();
+    let Bounds{min, max} = Bounds::of(&turned);
+    let (u_min, v_min, u_max, v_max) = (min.x - 1, min.y - 1, max.x + 1, max.y + 1);
+
+    // x = (u + v) / 2 and y = (u - v) / 2.
+    Bounds{
+        min: Point::new((u_min + v_min).div_euclid(2), (u_min - v_max).div_euclid(2)),
+        max: Point::new((u_max + v_max + 1).div_euclid(2), (u_max - v_min + 1).div_euclid(2)),
+    }
+}
+
+fn cross(o: Point, a: Point, b: Point) -> i128 {
+    let (ax, ay) = (i128::from(a.x - o.x), i128::from(a.y - o.y));
+    let (bx, by) = (i128::from(b.x - o.x), i128::from(b.y - o.y));
+    ax * by - ay * bx
+}
+
+/// Whether each point lies on the boundary of the convex hull, at a corner or along an edge.
+fn on_convex_hull(points: &[Point]) -> Vec<bool> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|p| (p.x, p.y));
+    sorted.dedup();
+
+    // Andrew's monotone chain, keeping only the corners.
+    let mut hull: Vec<Point> = vec![];
+    for pass in 0..2 {
+        let start = hull.len();
+        for &p in &sorted {
+            while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+        if pass == 0 {
+            sorted.reverse();
+        }
+    }
+
+    if hull.len() < 3 {
+        // Every point is on one line, which is all boundary.
+        return vec![true; points.len()];
+    }
+    points.iter().map(|&p| {
+        (0..hull.len()).any(|i| {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            cross(a, b, p) == 0 &&
+                a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) &&
+                a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y)
+        })
+    }).collect()
+}
+
+/// The bounding box of the points and of the corners of every bounded Euclidean cell.
+///
+/// Each corner is the centre of a circle through three points with no point inside; it is
+/// found in exact arithmetic by checking every such circle, so this takes O(n⁴) time.
+fn voronoi_bounds(points: &[Point], on_hull: &[bool]) -> Bounds {
+    let mut bounds = Bounds::of(points);
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                if on_hull[i] && on_hull[j] && on_hull[k] {
+                    continue;
+                }
+                if let Some((min, max)) = circumcentre(points, points[i], points[j], points[k]) {
+                    bounds.include(min);
+                    bounds.include(max);
+                }
+            }
+        }
+    }
+    bounds
+}
+
+/// The locations rounding down and up from the centre of the circle through `a`, `b` and
+/// `c`, provided no point lies inside it.
+fn circumcentre(points: &[Point], a: Point, b: Point, c: Point) -> Option<(Point, Point)> {
+    let mut d = 2 * cross(a, b, c);
+    if d == 0 {
+        return None;
+    }
+    let (bx, by) = (i128::from(b.x - a.x), i128::from(b.y - a.y));
+    let (cx, cy) = (i128::from(c.x - a.x), i128::from(c.y - a.y));
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    // The centre is a + (nx, ny) / d.
+    let (mut nx, mut ny) = (cy * b2 - by * c2, bx * c2 - cx * b2);
+    if d < 0 {
+        d = -d;
+        nx = -nx;
+        ny = -ny;
+    }
+
+    let inside = points.iter().any(|s| {
+        let (sx, sy) = (i128::from(s.x - a.x), i128::from(s.y - a.y));
+        (sx * sx + sy * sy) * d < 2 * (sx * nx + sy * ny)
+    });
+    if inside {
+        return None;
+    }
+
+    let floor = |n: i128| n.div_euclid(d) as i64;
+    let ceil = |n: i128| -((-n).div_euclid(d)) as i64;
+    Some((Point::new(a.x + floor(nx), a.y + floor(ny)), Point::new(a.x + ceil(nx), a.y + ceil(ny))))
+}
+
+pub fn largest_finite_area(points: &[Point], metric: Metric) -> LargestArea {
+    let regions = Regions::new(points, metric);
+
+    LargestArea{region: regions.largest_finite().cloned(), metric}
+}
+
+/// The locations whose total distance to all points is less than some limit.
+pub struct SafeRegion {
+    pub size: u64,
+    /// The smallest bounds holding the region, unless it is empty.
+    pub bounds: Option<Bounds>,
+}
+
+impl fmt::Display for SafeRegion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bounds {
+            Some(Bounds{min, max}) => write!(f, "{} ({} to {})", self.size, min, max),
+            None => write!(f, "{}", self.size),
+        }
+    }
+}
+
+impl Answer for SafeRegion {
+    fn value(&self) -> Json {
+        Json::from(self.size)
+    }
+
+    fn extras(&self) -> Vec<(&'static str, Json)> {
+        vec![
+            ("min_x", Json::from(self.bounds.map(|b| b.min.x))),
+            ("min_y", Json::from(self.bounds.map(|b| b.min.y))),
+            ("max_x", Json::from(self.bounds.map(|b| b.max.x))),
+            ("max_y", Json::from(self.bounds.map(|b| b.max.y))),
+        ]
+    }
+}
+
+/// The total distance from each of `lo..=hi` to all of `coords`, which must be sorted.
+fn distance_sums(coords: &[i64], lo: i64, hi: i64) -> Vec<u64> {
+    let mut prefix = vec![0i128];
+    for &c in coords {
+        prefix.push(prefix[prefix.len() - 1] + i128::from(c));
+    }
+    let (n, total) = (coords.len() as i128, prefix[coords.len()]);
+
+    (lo..=hi).map(|x| {
+        let below = coords.partition_point(|&c| c <= x);
+        let (k, x) = (below as i128, i128::from(x));
+        (x * k - prefix[below] + (total - prefix[below]) - x * (n - k)) as u64
+    }).collect()
+}
+
+/// The locations whose total Manhattan distance to all points is less than `max_dist`.
+///
+/// Such a location lies less than `max_dist / n` outside the points' bounding box, since it
+/// is at least that far from every point along one axis. The total distance splits into a
+/// sum over x plus a sum over y, so each is found once per column and row, and the rows
+/// under the limit for each column are counted by a search over the sorted row sums.
+pub fn safe_region(points: &[Point], max_dist: u64) -> SafeRegion {
+    let margin = (max_dist / points.len() as u64) as i64;
+    let Bounds{min, max} = Bounds::of(points);
+    let (x_lo, y_lo) = (min.x - margin, min.y - margin);
+
+    let mut xs: Vec<i64> = points.iter().map(|p| p.x).collect();
+    let mut ys: Vec<i64> = points.iter().map(|p| p.y).collect();
+    xs.sort_unstable();
+    ys.sort_unstable();
+    let x_sums = distance_sums(&xs, x_lo, max.x + margin);
+    let y_sums = distance_sums(&ys, y_lo, max.y + margin);
+
+    let mut sorted_y_sums = y_sums.clone();
+    sorted_y_sums.sort_unstable();
+    let size = x_sums.iter().
+        map(|&sx| sorted_y_sums.partition_point(|&sy| sx + sy < max_dist) as u64).
+        sum();
+
+    // Both sums are convex, so the columns and rows reaching under the limit are contiguous.
+    let (min_x_sum, min_y_sum) = (x_sums.iter().min().unwrap(), y_sums.iter().min().unwrap());
+    let columns = within(&x_sums, |sx| sx + min_y_sum < max_dist);
+    let rows = within(&y_sums, |sy| sy + min_x_sum < max_dist);
+    let bounds = columns.and_then(|(left, right)| rows.map(|(top, bottom)| Bounds{
+        min: Point::new(x_lo + left as i64, y_lo + top as i64),
+        max: Point::new(x_lo + right as i64, y_lo + bottom as i64),
+    }));
+
+    SafeRegion{size, bounds}
+}
+
+/// The first and last positions of `sums` satisfying `keep`.
+fn within<F: Fn(u64) -> bool>(sums: &[u64], keep: F) -> Option<(usize, usize)> {
+    let first = sums.iter().position(|&s| keep(s))?;
+    let last = sums.iter().rposition(|&s| keep(s))?;
+    Some((first, last))
+}
+
+pub struct Day6 {
+    pub max_dist: u64,
+    pub metric: Metric,
+}
+
+impl Default for Day6 {
+    fn default() -> Day6 {
+        Day6{max_dist: 10000, metric: Metric::Manhattan}
+    }
+}
+
+impl Solver for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = Vec<Point>;
+    type Part1 = LargestArea;
+    type Part2 = SafeRegion;
+
+    fn parse(&self, input: &str) -> Result<Vec<Point>, ParseError> {
+        let points = parse_lines(input, parse_point)?;
+        if points.is_empty() {
+            return Err(ParseError::at(input, &input[input.len()..], "a coordinate <x>, <y>"));
+        }
+        Ok(points)
+    }
+
+    fn part1(&self, points: &Vec<Point>) -> LargestArea {
+        largest_finite_area(points, self.metric)
+    }
+
+    fn part2(&self, points: &Vec<Point>) -> SafeRegion {
+        safe_region(points, self.max_dist)
+    }
+
+    /// Takes the maximum total distance for part 2, then the metric for part 1.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        if let Some(param) = params.first() {
+            self.max_dist = common::parse_param(param, "max distance")?;
+        }
+        if let Some(param) = params.get(1) {
+            self.metric = Metric::by_name(param).ok_or_else(|| format!(
+                "Invalid metric: {} (expected manhattan, chebyshev or euclidean)", param))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    const METRICS: [Metric; 3] = [Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean];
+
+    fn points(coords: &[(i64, i64)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    fn nearest(points: &[Point], metric: Metric, q: Point) -> Owner {
+        let distances: Vec<u64> = points.iter().map(|&p| metric.distance(p, q)).collect();
+        let best = *distances.iter().min().unwrap();
+        let mut nearest = (0..points.len()).filter(|&i| distances[i] == best);
+        match (nearest.next(), nearest.next()) {
+            (Some(i), None) => Owner::Point(i),
+            _ => Owner::Tie,
+        }
+    }
+
+    fn grown(bounds: &Bounds, by: i64) -> Bounds {
+        Bounds{
+            min: Point::new(bounds.min.x - by, bounds.min.y - by),
+            max: Point::new(bounds.max.x + by, bounds.max.y + by),
+        }
+    }
+
+    /// Checks `Regions::new` against the nearest points of every location in a box around
+    /// its bounds; a region owning a location in the outer band of that box is infinite.
+    fn check_against_scan(points: &[Point], metric: Metric) {
+        let regions = Regions::new(points, metric);
+        let bounds = regions.bounds;
+        let context = format!("{:?} {}", points, metric.name());
+        assert!(points.iter().all(|&p| bounds.contains(p)), "{}", context);
+
+        let spread = Bounds::of(points);
+        let margin = spread.width().max(spread.height()) as i64 + 2;
+        let (inner, outer) = (grown(&bounds, margin), grown(&bounds, 2 * margin));
+
+        let mut areas = vec![0; points.len()];
+        let mut areas_within = vec![0; points.len()];
+        let mut infinite = vec![false; points.len()];
+        let mut ties = vec![];
+        for y in outer.min.y..=outer.max.y {
+            for x in outer.min.x..=outer.max.x {
+                let q = Point::new(x, y);
+                let owner = nearest(points, metric, q);
+                if bounds.contains(q) {
+                    assert_eq!(regions.owner(q), Some(owner), "{} at {:?}", context, q);
+                    match owner {
+                        Owner::Point(i) => areas_within[i] += 1,
+                        _ => ties.push(q),
+                    }
+                } else {
+                    assert_eq!(regions.owner(q), None, "{} at {:?}", context, q);
+                }
+                if let Owner::Point(i) = owner {
+                    areas[i] += 1;
+                    infinite[i] |= !inner.contains(q);
+                }
+            }
+        }
+
+        for (i, region) in regions.regions.iter().enumerate() {
+            assert_eq!(region.point, points[i], "{}", context);
+            assert_eq!(region.infinite, infinite[i], "{} point {}", context, i);
+            assert_eq!(region.area, areas_within[i], "{} point {}", context, i);
+            if !region.infinite {
+                assert_eq!(region.area, areas[i], "{} point {} spills out", context, i);
+            }
+        }
+        assert_eq!(regions.ties, ties, "{}", context);
+    }
+
+    #[test]
+    fn regions_match_a_scan_of_every_location() {
+        let mut rng = Rng::new(6);
+        for _ in 0..100 {
+            let n = 1 + rng.below(8) as usize;
+            // Coordinates either side of zero, close enough together that points often
+            // coincide or line up.
+            let random: Vec<Point> = (0..n).
+                map(|_| Point::new(rng.below(13) as i64 - 6, rng.below(13) as i64 - 6)).
+                collect();
+            for &metric in &METRICS {
+                check_against_scan(&random, metric);
+            }
+        }
+    }
+
+    #[test]
+    fn example_areas_and_ties() {
+        let example = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
+        let regions = Regions::new(&example, Metric::Manhattan);
+        let areas: Vec<(u64, bool)> = regions.regions.iter().map(|r| (r.area, r.infinite)).collect();
+        assert_eq!(areas, vec![(7, true), (9, true), (12, true), (9, false), (17, false), (10, true)]);
+
+        // The locations shown as `.` in the puzzle, within the bounding box.
+        let ties = points(&[(5, 1), (1, 4), (2, 5), (3, 6), (8, 6), (3, 7), (3, 8), (3, 9)]);
+        assert_eq!(regions.ties, ties);
+        for &tie in &ties {
+            assert_eq!(regions.owner(tie), Some(Owner::Tie));
+        }
+        assert_eq!(regions.owner(Point::new(4, 4)), Some(Owner::Point(3)));
+        assert_eq!(regions.owner(Point::new(0, 0)), None);
+        assert_eq!(regions.largest_finite().map(|r| r.point), Some(Point::new(5, 5)));
+    }
+
+    #[test]
+    fn ties_own_no_area() {
+        // Midway between two points, and equally far from the four corners of a square.
+        let pair = points(&[(-2, 0), (2, 0)]);
+        let square = points(&[(-3, -3), (3, -3), (-3, 3), (3, 3)]);
+        for &metric in &METRICS {
+            let regions = Regions::new(&pair, metric);
+            assert_eq!(regions.owner(Point::new(0, 0)), Some(Owner::Tie));
+            assert_eq!(regions.owner(Point::new(-1, 0)), Some(Owner::Point(0)));
+            let regions = Regions::new(&square, metric);
+            assert_eq!(regions.owner(Point::new(0, 0)), Some(Owner::Tie));
+            let owned: u64 = regions.regions.iter().map(|r| r.area).sum();
+            let locations = (regions.bounds.width() * regions.bounds.height()) as u64;
+            assert_eq!(owned + regions.ties.len() as u64, locations, "{}", metric.name());
+        }
+    }
+
+    #[test]
+    fn duplicate_points_own_nothing() {
+        let duplicated = points(&[(-3, -2), (4, 1), (-3, -2), (0, 5)]);
+        for &metric in &METRICS {
+            check_against_scan(&duplicated, metric);
+            let regions = Regions::new(&duplicated, metric);
+            for &i in &[0, 2] {
+                assert_eq!((regions.regions[i].area, regions.regions[i].infinite), (0, false));
+                assert_eq!(regions.owner(duplicated[i]), Some(Owner::Tie));
+            }
+        }
+
+        let single = points(&[(-2, 7), (-2, 7)]);
+        for &metric in &METRICS {
+            check_against_scan(&single, metric);
+            assert_eq!(Regions::new(&single, metric).largest_finite(), Some(&Region{
+                point: Point::new(-2, 7), area: 0, infinite: false}));
+        }
+    }
+
+    #[test]
+    fn collinear_points() {
+        let lines = [
+            points(&[(-4, 0), (0, 0), (3, 0), (9, 0)]),
+            points(&[(2, -5), (2, -1), (2, 4)]),
+            points(&[(-3, -3), (-1, -1), (0, 0), (4, 4)]),
+            points(&[(-2, 1), (0, 2), (4, 4), (6, 5)]),
+            points(&[(0, 0), (3, -1), (6, -2), (1, 1)]),
+        ];
+        for line in &lines {
+            for &metric in &METRICS {
+                check_against_scan(line, metric);
+            }
+        }
+        // Every Euclidean cell of points in a line runs off to either side.
+        for line in &lines[..4] {
+            let regions = Regions::new(line, Metric::Euclidean);
+            assert!(regions.regions.iter().all(|r| r.infinite));
+        }
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day6;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day6::Day6::default, env!("CARGO_MANIFEST_DIR"));
+}
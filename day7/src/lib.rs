@@ -0,0 +1,211 @@
+extern crate common;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::cmp::Ordering;
+
+use common::{Solver, ParseError};
+use common::parse::parse_lines;
+
+#[derive(Clone)]
+pub struct StepPlan {
+    steps: HashSet<String>,
+    preconditions: HashMap<String,HashSet<String>>,
+    conditioned_by: HashMap<String,Vec<String>>,
+}
+
+const STEP_GRAMMAR: &str = "Step <A> must be finished before step <B> can begin.";
+
+/// Parses one instruction into its precondition step and the step it conditions.
+pub fn parse_step(line: &str) -> Result<(String, String), ParseError> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() != 10 || tokens[0] != "Step" || tokens[6] != "step" {
+        return Err(ParseError::at(line, line.trim(), STEP_GRAMMAR));
+    }
+
+    for &step in &[tokens[1], tokens[7]] {
+        if step.len() != 1 || !step.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(ParseError::at(line, step, "a step name from A to Z"));
+        }
+    }
+
+    Ok((tokens[1].to_string(), tokens[7].to_string()))
+}
+
+pub fn parse_step_plan(input: &str) -> Result<StepPlan, ParseError> {
+    let mut steps = HashSet::new();
+    let mut preconditions = HashMap::new();
+    let mut conditioned_by = HashMap::new();
+
+    for (precondition_step, step) in parse_lines(input, parse_step)? {
+
+        let precondition_set = preconditions.entry(step.clone())
+            .or_insert(HashSet::new());
+        precondition_set.insert(precondition_step.clone());
+
+        let conditioned_by_list = conditioned_by.entry(precondition_step.clone())
+            .or_insert(vec![]);
+        conditioned_by_list.push(step.clone());
+
+        steps.insert(step.to_string());
+        steps.insert(precondition_step.to_string());
+    }
+
+    for step in &steps {
+        preconditions.entry(step.clone()).or_insert(HashSet::new());
+        conditioned_by.entry(step.clone()).or_insert(vec![]);
+    }
+
+    Ok(StepPlan{steps, preconditions, conditioned_by})
+}
+
+pub fn get_ordered_steps(step_plan: StepPlan) -> String {
+    let mut steps = step_plan.steps;
+    let mut preconditions = step_plan.preconditions;
+    let conditioned_by = step_plan.conditioned_by;
+
+    let mut ordered_steps = String::from("");
+
+    while !steps.is_empty() {
+        let step = steps.iter().min_by(|a,b| {
+            let pre_a = preconditions.get(&a.to_string()).unwrap().len();
+            let pre_b = preconditions.get(&b.to_string()).unwrap().len();
+
+            if pre_a < pre_b {
+                Ordering::Less
+            } else if pre_a > pre_b {
+                Ordering::Greater
+            } else {
+                a.cmp(b)
+            }
+        }).unwrap().clone();
+        ordered_steps.push_str(&step);
+
+        let csteps = conditioned_by.get(&step.to_string()).unwrap();
+
+        for cstep in csteps{
+            let precondition_set = preconditions.get_mut(&cstep.to_string()).unwrap();
+            precondition_set.remove(&step.clone());
+        }
+        steps.remove(&step.clone());
+    }
+
+    ordered_steps
+}
+
+pub fn get_finish_time(step_plan: StepPlan, worker_count: u32, base_duration: u32) -> u32 {
+    let mut steps = step_plan.steps;
+    let mut preconditions = step_plan.preconditions;
+    let conditioned_by = step_plan.conditioned_by;
+
+    let step_durations: HashMap<String,u32> = (b'A'..=b'Z')
+        .map(|c| c as char)
+        .enumerate()
+        .map(|(i,c)| (c.to_string() ,(i as u32) + base_duration + 1))
+        .collect();
+
+    let mut seconds = 0;
+    let mut busy_workers: HashMap<String,u32> = HashMap::new();
+    let mut free_workers = worker_count;
+
+    loop {
+        // Mark the passage of time
+        seconds += 1;
+
+        // Update how much time each worker must continuing working
+        for (step, dur) in busy_workers.iter_mut() {
+            *dur -= 1;
+            if *dur == 0 {
+                free_workers += 1;
+
+                // Remove any preconditions created by this step
+                let csteps = conditioned_by.get(&step.to_string()).unwrap();
+                for cstep in csteps{
+                    let precondition_set = preconditions.get_mut(&cstep.to_string()).unwrap();
+                    precondition_set.remove(&step.clone());
+                }
+            }
+        }
+
+        // Remove workers who are no longer busy
+        busy_workers.retain(|_,dur| *dur != 0);
+
+        // Look for work to perform if workers are free
+        while free_workers > 0 && !steps.is_empty() {
+            let step = steps.iter().min_by(|a,b| {
+                let pre_a = preconditions.get(&a.to_string()).unwrap().len();
+                let pre_b = preconditions.get(&b.to_string()).unwrap().len();
+
+                if pre_a < pre_b {
+                    Ordering::Less
+                } else if pre_a > pre_b {
+                    Ordering::Greater
+                } else {
+                    a.cmp(b)
+                }
+            }).unwrap().clone();
+
+            // What precondtions does this step have before we can perform it?
+            let precondition_count = preconditions.get(&step.to_string()).unwrap().len();
+            if precondition_count == 0 {
+                // Start working
+                let duration = step_durations.get(&step.to_string()).unwrap();
+                busy_workers.insert(step.clone(), *duration);
+
+                // Remove a work step and a free worker
+                free_workers -= 1;
+                steps.remove(&step.clone());
+            } else {
+                // We have preconditions that must be met before we continue
+                break;
+            }
+        }
+
+        // Are we done yet?
+        if steps.is_empty() && busy_workers.is_empty() { break };
+    }
+
+    seconds - 1
+}
+
+pub struct Day7 {
+    pub worker_count: u32,
+    pub base_duration: u32,
+}
+
+impl Default for Day7 {
+    fn default() -> Day7 {
+        Day7{worker_count: 5, base_duration: 60}
+    }
+}
+
+impl Solver for Day7 {
+    const DAY: u32 = 7;
+
+    type Input = StepPlan;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(&self, input: &str) -> Result<StepPlan, ParseError> {
+        parse_step_plan(input)
+    }
+
+    fn part1(&self, step_plan: &StepPlan) -> String {
+        get_ordered_steps(step_plan.clone())
+    }
+
+    fn part2(&self, step_plan: &StepPlan) -> u32 {
+        get_finish_time(step_plan.clone(), self.worker_count, self.base_duration)
+    }
+
+    /// Takes the worker count and base step duration for part 2.
+    fn set_params(&mut self, params: &[String]) -> Result<(), String> {
+        if let Some(param) = params.first() {
+            self.worker_count = common::parse_param(param, "worker count")?;
+        }
+        if let Some(param) = params.get(1) {
+            self.base_duration = common::parse_param(param, "base duration")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day7;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(day7::Day7::default, env!("CARGO_MANIFEST_DIR"));
+}
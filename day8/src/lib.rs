@@ -0,0 +1,198 @@
+extern crate common;
+
+use std::char;
+
+use std::fmt::Error;
+use std::fmt::Formatter;
+
+use common::{Solver, ParseError};
+use common::parse::parse_number;
+
+pub struct Node {
+    pub label: String,
+    pub children: Vec<usize>,
+    pub metadata_entries: Vec<usize>,
+}
+
+pub struct NodeTree {
+    pub nodes: Vec<Node>,
+}
+
+impl Node {
+    pub fn value(&self, node_tree: &NodeTree) -> u32 {
+        if self.children.is_empty() {
+            self.metadata_entries.iter().
+                map(|entry| *entry as u32).
+                sum()
+        } else {
+            let children_count = self.children.len();
+            self.metadata_entries.iter().
+                filter(|child_idx| **child_idx <= children_count).
+                map(|child_idx| self.children[*child_idx - 1]).
+                map(|idx| node_tree.nodes[idx].value(node_tree)).
+                sum()
+        }
+    }
+}
+
+impl NodeTree {
+    pub fn metadata_checksum(&self) -> u32 {
+        self.nodes.iter().
+            flat_map(|node| node.metadata_entries.iter()).
+            map(|v| *v as u32).
+            sum()
+    }
+
+    pub fn root_value(&self) -> u32 {
+        self.nodes.first().map_or(0, |root| root.value(self))
+    }
+}
+
+impl std::fmt::Display for NodeTree {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        for node in self.nodes.iter() {
+            writeln!(f, "{}", node.label)?;
+            let children = node.children.iter().
+                map(|idx| self.nodes[*idx].label.to_string()).
+                collect::<Vec<String>>().
+                join(", ");
+            writeln!(f, "   - Children: [{}]", children)?;
+
+            let entries = node.metadata_entries.iter().
+                map(|c| c.to_string()).
+                collect::<Vec<String>>().
+                join(", ");
+            writeln!(f, "   - Entries: [{}]", entries)?;
+
+            writeln!(f, "   - Value: {}", node.value(self))?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Parses the next whitespace separated token of `s` as a count or entry.
+fn next_value<'a, I>(s: &str, values: &mut I, expected: &str) -> Result<usize, ParseError>
+    where I: Iterator<Item = &'a str> {
+    match values.next() {
+        Some(token) => parse_number(s, token, expected),
+        None => Err(ParseError::at(s, &s[s.len()..], expected)),
+    }
+}
+
+impl std::str::FromStr for NodeTree {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, <Self as std::str::FromStr>::Err> {
+        let mut values = s.split_whitespace();
+
+        let mut stack: Vec<(usize, usize, usize)> = Vec::with_capacity(s.len());
+        let mut nodes: Vec<Node> = vec![];
+
+        // The input holds exactly one tree: read its root, then each node's
+        // children before its metadata entries until the root is finished
+        loop {
+            // Do we have a parent on the stack?
+            let mut pop_stack = false;
+            if let Some(t) = stack.last_mut() {
+                let parent_id = t.0;
+
+                // Have we finished processing all children for this node?
+                if t.1 == 0 {
+                    for _ in 0..t.2 {
+                        let entry = next_value(s, &mut values, "a metadata entry")?;
+                        nodes[parent_id].metadata_entries.push(entry);
+                    }
+
+                    // Finished with this node
+                    pop_stack = true;
+                } else {
+                    // Decrease the number of children to process
+                    t.1 -= 1;
+
+                    // Add index to parent for child we are about to create
+                    let child_id = nodes.len();
+                    nodes[parent_id].children.push(child_id);
+                }
+            }
+
+            if pop_stack {
+                // The tree is complete once its root is
+                stack.pop().unwrap();
+                if stack.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            // Create a new node
+            let node_index = nodes.len();
+            let label = char::from_u32((b'A' as u32) + (node_index as u32)).
+                unwrap().to_string();
+            nodes.push(Node{label: label.clone(), children: vec![], metadata_entries: vec![]});
+
+            let children_count = next_value(s, &mut values, "a child node count")?;
+            let metadata_entry_count = next_value(s, &mut values, "a metadata entry count")?;
+            stack.push((node_index, children_count, metadata_entry_count));
+        }
+
+        if let Some(token) = values.next() {
+            return Err(ParseError::at(s, token, "the end of the input"));
+        }
+
+        Ok(NodeTree{nodes})
+    }
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = NodeTree;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(&self, input: &str) -> Result<NodeTree, ParseError> {
+        input.parse()
+    }
+
+    fn part1(&self, node_tree: &NodeTree) -> u32 {
+        node_tree.metadata_checksum()
+    }
+
+    fn part2(&self, node_tree: &NodeTree) -> u32 {
+        node_tree.root_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> (usize, String) {
+        let e = input.parse::<NodeTree>().err().expect(input);
+        (e.column, e.expected)
+    }
+
+    #[test]
+    fn parses_one_tree() {
+        let tree: NodeTree = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2".parse().unwrap();
+        assert_eq!(tree.nodes.len(), 4);
+        assert_eq!((tree.metadata_checksum(), tree.root_value()), (138, 66));
+        let leaf: NodeTree = "0 1 5\n".parse().unwrap();
+        assert_eq!((leaf.nodes.len(), leaf.root_value()), (1, 5));
+    }
+
+    #[test]
+    fn rejects_trailing_input() {
+        assert_eq!(error("0 1 5 0 1 7"), (7, "the end of the input".to_string()));
+        assert_eq!(error("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2 5"), (37, "the end of the input".to_string()));
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        assert_eq!(error(""), (1, "a child node count".to_string()));
+        assert_eq!(error("1 1 0 1"), (8, "a metadata entry".to_string()));
+        assert_eq!(error("1 1 0 1 7"), (10, "a metadata entry".to_string()));
+    }
+}
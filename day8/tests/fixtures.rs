@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day8;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day8::Day8, env!("CARGO_MANIFEST_DIR"));
+}
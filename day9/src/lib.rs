@@ -0,0 +1,212 @@
+extern crate common;
+
+use std::fmt;
+use std::collections::LinkedList;
+
+use common::{Solver, ParseError, Answer, Json};
+use common::parse::parse_number;
+
+
+pub struct Loop<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Default for Loop<T> {
+    fn default() -> Loop<T> {
+        Loop::new()
+    }
+}
+
+impl<T> Loop<T> {
+    pub fn new() -> Loop<T> {
+        Loop{list: LinkedList::new()}
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.list.push_back(elem)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    fn rotate_front_to_back(&mut self, steps: u32) {
+        for _ in 0..steps {
+            let elem = self.list.pop_front().unwrap();
+            self.list.push_back(elem);
+        }
+    }
+
+    fn rotate_back_to_front(&mut self, steps: u32) {
+        for _ in 0..steps {
+            let elem = self.list.pop_back().unwrap();
+            self.list.push_front(elem);
+        }
+    }
+
+    pub fn rotate_clockwise(&mut self, steps: u32) {
+        let n = self.list.len() as u32;
+
+        if n > 1 {
+            let right_steps = steps % n;
+            let left_steps = n - right_steps;
+            if right_steps <= left_steps {
+                self.rotate_back_to_front(right_steps);
+            } else {
+                self.rotate_front_to_back(left_steps);
+            }
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&mut self, steps: u32) {
+        let n = self.list.len() as u32;
+
+        if n > 1 {
+            let left_steps = steps % n;
+            let right_steps = n - left_steps;
+            if right_steps <= left_steps {
+                self.rotate_back_to_front(right_steps);
+            } else {
+                self.rotate_front_to_back(left_steps);
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Loop<T> where T: std::fmt::Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let last_index = self.list.len() - 1;
+        for (index, elem) in self.list.iter().enumerate() {
+            if index == last_index {
+                write!(f, "({})", elem)?;
+            } else {
+                write!(f, "{}  ", elem)?;
+            }
+        }
+        write!(f, "")
+    }
+}
+
+pub fn winning_score(player_count: usize, last_marble_value: u32) -> u32 {
+    let mut marble_circle: Loop<u32> = Loop::new();
+    let mut player_scores = vec![0u32; player_count];
+
+    let mut current_player = 0;
+    let mut current_marble_value = 0u32;
+    marble_circle.push(current_marble_value);
+
+    while current_marble_value <= last_marble_value {
+        current_marble_value += 1;
+
+        if current_marble_value.is_multiple_of(23) {
+            player_scores[current_player] += current_marble_value;
+            marble_circle.rotate_clockwise(7);
+            let removed_marble_value = marble_circle.pop().expect("Missing marble!");
+            player_scores[current_player] += removed_marble_value;
+            marble_circle.rotate_counter_clockwise(1);
+
+        } else {
+            marble_circle.rotate_counter_clockwise(1);
+            marble_circle.push(current_marble_value);
+        }
+
+        current_player = (current_player + 1) % player_count;
+    }
+
+    *player_scores.iter().max().unwrap()
+}
+
+pub struct MarbleGame {
+    pub player_count: usize,
+    pub last_marble_value: u32,
+}
+
+pub struct WinningScore(pub u32);
+
+impl fmt::Display for WinningScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Winning Score: {}", self.0)
+    }
+}
+
+impl Answer for WinningScore {
+    fn value(&self) -> Json {
+        Json::from(self.0)
+    }
+}
+
+const GAME_GRAMMAR: [&str; 8] = ["", "players;", "last", "marble", "is", "worth", "", "points"];
+const GAME_EXPECTED: &str = "<players> players; last marble is worth <points> points";
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    const DAY: u32 = 9;
+
+    type Input = MarbleGame;
+    type Part1 = WinningScore;
+    type Part2 = WinningScore;
+
+    /// Accepts either "10 1618" or the puzzle's "10 players; last marble is worth 1618 points".
+    fn parse(&self, input: &str) -> Result<MarbleGame, ParseError> {
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let grammar: &[&str] = if tokens.len() == 2 { &["", ""] } else { &GAME_GRAMMAR };
+
+        // Empty grammar words stand for the two numbers.
+        for (i, word) in grammar.iter().enumerate() {
+            match tokens.get(i) {
+                Some(token) if word.is_empty() || token == word => {},
+                Some(token) => return Err(ParseError::at(input, token, GAME_EXPECTED)),
+                None => return Err(ParseError::at(input, &input[input.len()..], GAME_EXPECTED)),
+            }
+        }
+        if let Some(token) = tokens.get(grammar.len()) {
+            return Err(ParseError::at(input, token, "the end of the input"));
+        }
+
+        let mut numbers = grammar.iter().zip(&tokens).filter(|(word, _)| word.is_empty()).map(|(_, &token)| token);
+        let (players, points) = (numbers.next().unwrap(), numbers.next().unwrap());
+        let player_count = parse_number(input, players, "a number of players")?;
+        if player_count == 0 {
+            return Err(ParseError::at(input, players, "at least one player"));
+        }
+        let last_marble_value = parse_number(input, points, "a last marble value")?;
+
+        Ok(MarbleGame{player_count, last_marble_value})
+    }
+
+    fn part1(&self, game: &MarbleGame) -> WinningScore {
+        WinningScore(winning_score(game.player_count, game.last_marble_value))
+    }
+
+    fn part2(&self, game: &MarbleGame) -> WinningScore {
+        WinningScore(winning_score(game.player_count, game.last_marble_value * 100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> (usize, String) {
+        let e = Day9.parse(input).err().expect(input);
+        (e.column, e.expected.to_string())
+    }
+
+    #[test]
+    fn parses_either_form() {
+        let game = Day9.parse("10 players; last marble is worth 1618 points\n").unwrap();
+        assert_eq!((game.player_count, game.last_marble_value), (10, 1618));
+        let game = Day9.parse("10 1618").unwrap();
+        assert_eq!((game.player_count, game.last_marble_value), (10, 1618));
+    }
+
+    #[test]
+    fn rejects_anything_else() {
+        assert_eq!(error("garbage 1 2").0, 9);
+        assert_eq!(error("10 players; last marble is worth 1618 pts").0, 39);
+        assert_eq!(error("10 players; last marble is worth 1618 points!!").0, 39);
+        assert_eq!(error("10 1618 7"), (4, GAME_EXPECTED.to_string()));
+        assert_eq!(error("0 players; last marble is worth 10 points"), (1, "at least one player".to_string()));
+    }
+}
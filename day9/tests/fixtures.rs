@@ -0,0 +1,7 @@
+extern crate common;
+extern crate day9;
+
+#[test]
+fn fixtures_match_expected_answers() {
+    common::fixtures::check(|| day9::Day9, env!("CARGO_MANIFEST_DIR"));
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use common::{Answer, Json};
+
+use direction::Direction;
+
+/// A cell on a grid with its origin at the top left; ordered in reading order,
+/// top to bottom and then left to right.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub fn new(row: usize, col: usize) -> Coord {
+        Coord{row, col}
+    }
+
+    /// The neighbouring cell in `direction`, unless that would leave the first row or column.
+    pub fn step(self, direction: Direction) -> Option<Coord> {
+        self.offset(direction.offset())
+    }
+
+    /// This cell moved by `(rows, cols)`, unless that would leave the first row or column.
+    pub fn offset(self, (rows, cols): (i64, i64)) -> Option<Coord> {
+        let row = self.row as i64 + rows;
+        let col = self.col as i64 + cols;
+        if row < 0 || col < 0 {
+            None
+        } else {
+            Some(Coord{row: row as usize, col: col as usize})
+        }
+    }
+
+    /// The up to four orthogonal neighbours, in reading order.
+    pub fn neighbours(self) -> impl Iterator<Item = Coord> {
+        Direction::ALL.iter().filter_map(move |&d| self.step(d))
+    }
+
+    /// The up to eight surrounding cells, in reading order.
+    pub fn neighbours8(self) -> impl Iterator<Item = Coord> {
+        OFFSETS8.iter().filter_map(move |&offset| self.offset(offset))
+    }
+
+    pub fn manhattan_distance(self, other: Coord) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+/// Offsets of the eight surrounding cells, in reading order.
+pub(crate) const OFFSETS8: [(i64, i64); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1),
+];
+
+/// Shown as `x,y`, i.e. column then row.
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.col, self.row)
+    }
+}
+
+impl Answer for Coord {
+    fn value(&self) -> Json {
+        Json::from(self.to_string())
+    }
+}
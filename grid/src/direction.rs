@@ -0,0 +1,98 @@
+use std::fmt;
+
+use self::Direction::*;
+
+/// A step on a grid where rows grow downward.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    North, West, East, South
+}
+
+impl Direction {
+    /// The four directions in reading order of the cells they lead to.
+    pub const ALL: [Direction; 4] = [North, West, East, South];
+
+    /// Row and column offsets, in that order.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            North => (-1, 0),
+            West => (0, -1),
+            East => (0, 1),
+            South => (1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            North => West,
+            West => South,
+            South => East,
+            East => North,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            North => East,
+            East => South,
+            South => West,
+            West => North,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            North => South,
+            South => North,
+            West => East,
+            East => West,
+        }
+    }
+
+    /// The arrow drawn for something heading this way, e.g. a cart.
+    pub fn to_char(self) -> char {
+        match self {
+            North => '^',
+            West => '<',
+            East => '>',
+            South => 'v',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(North),
+            '<' => Some(West),
+            '>' => Some(East),
+            'v' => Some(South),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_and_reversals_agree() {
+        for &d in &Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_left().turn_left(), d.reverse());
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.reverse().reverse(), d);
+
+            let (rows, cols) = d.offset();
+            assert_eq!(d.reverse().offset(), (-rows, -cols));
+            assert_eq!(Direction::from_char(d.to_char()), Some(d));
+        }
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(Direction::from_char('x'), None);
+    }
+}
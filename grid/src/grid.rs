@@ -0,0 +1,219 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use common::ParseError;
+
+use coord::Coord;
+use direction::Direction;
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid{width, height, cells: vec![fill; width * height]}
+    }
+
+    /// Parses one row per line and one cell per character, rejecting characters for which
+    /// `cell` gives `None`. Rows shorter than the longest are padded as if with spaces.
+    pub fn parse<F>(s: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+        where F: Fn(char) -> Option<T> {
+        let lines: Vec<&str> = s.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        let token = &line[i..i + c.len_utf8()];
+                        return Err(ParseError::at(line, token, expected).offset_lines(row));
+                    },
+                }
+            }
+
+            let padding = width - line.chars().count();
+            if padding > 0 {
+                let blank = cell(' ').ok_or_else(|| {
+                    ParseError::at(line, &line[line.len()..], expected).offset_lines(row)
+                })?;
+                cells.extend((0..padding).map(|_| blank.clone()));
+            }
+        }
+
+        Ok(Grid{width, height: lines.len(), cells})
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.row < self.height && coord.col < self.width
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self.cells[coord.row * self.width + coord.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self.cells[coord.row * self.width + coord.col])
+        } else {
+            None
+        }
+    }
+
+    /// The neighbouring cell in `direction`, if it lies on the grid.
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        coord.step(direction).filter(|&c| self.contains(c))
+    }
+
+    /// The orthogonal neighbours on the grid, in reading order.
+    pub fn neighbours<'a>(&'a self, coord: Coord) -> impl Iterator<Item = Coord> + 'a {
+        coord.neighbours().filter(move |&c| self.contains(c))
+    }
+
+    /// The surrounding cells on the grid, in reading order.
+    pub fn neighbours8<'a>(&'a self, coord: Coord) -> impl Iterator<Item = Coord> + 'a {
+        coord.neighbours8().filter(move |&c| self.contains(c))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell with its coordinate, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let width = self.width;
+        self.cells.iter().
+            enumerate().
+            map(move |(i, cell)| (Coord{row: i / width, col: i % width}, cell))
+    }
+
+    /// Draws the grid one row per line, choosing each cell's character with `draw`.
+    pub fn render<F>(&self, draw: F) -> String
+        where F: Fn(Coord, &T) -> char {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for (coord, cell) in self.iter() {
+            s.push(draw(coord, cell));
+            if coord.col + 1 == self.width {
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        assert!(self.contains(coord), "{} lies outside a {}x{} grid", coord, self.width, self.height);
+        &self.cells[coord.row * self.width + coord.col]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        assert!(self.contains(coord), "{} lies outside a {}x{} grid", coord, self.width, self.height);
+        &mut self.cells[coord.row * self.width + coord.col]
+    }
+}
+
+/// Renders each cell with its `Display`, one row per line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walls(s: &str) -> Result<Grid<bool>, ParseError> {
+        Grid::parse(s, "# or .", |c| match c {
+            '#' => Some(true),
+            '.' | ' ' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn parses_and_pads_short_rows() {
+        let grid = walls("#.#\n#\n..").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.render(|_, &wall| if wall { '#' } else { '.' }), "#.#\n#..\n...\n");
+        assert!(grid[Coord::new(0, 2)]);
+        assert_eq!(grid.get(Coord::new(1, 1)), Some(&false));
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+    }
+
+    #[test]
+    fn rejects_unknown_cells_where_they_occur() {
+        let error = walls("#.\n.x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let strict = Grid::parse("ab\nb", "a or b", |c| if c == 'a' || c == 'b' { Some(c) } else { None });
+        assert_eq!(strict.unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::new(3, 2, 0);
+        let corner: Vec<Coord> = grid.neighbours(Coord::new(0, 0)).collect();
+        assert_eq!(corner, vec![Coord::new(0, 1), Coord::new(1, 0)]);
+        assert_eq!(grid.neighbours8(Coord::new(1, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Coord::new(0, 1)).count(), 5);
+        assert_eq!(grid.step(Coord::new(1, 2), Direction::East), None);
+        assert_eq!(grid.step(Coord::new(1, 2), Direction::North), Some(Coord::new(0, 2)));
+        assert_eq!(Coord::new(0, 0).step(Direction::North), None);
+    }
+
+    #[test]
+    fn iterates_and_indexes_in_reading_order() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid[Coord::new(1, 0)] = 3;
+        *grid.get_mut(Coord::new(0, 1)).unwrap() = 7;
+        assert!(grid.get_mut(Coord::new(2, 0)).is_none());
+
+        let cells: Vec<(Coord, i32)> = grid.iter().map(|(c, &v)| (c, v)).collect();
+        assert_eq!(cells, vec![
+            (Coord::new(0, 0), 0), (Coord::new(0, 1), 7),
+            (Coord::new(1, 0), 3), (Coord::new(1, 1), 0),
+        ]);
+        assert_eq!(grid.to_string(), "07\n30\n");
+        assert_eq!(Coord::new(1, 0).to_string(), "0,1");
+        assert_eq!(Coord::new(0, 0).manhattan_distance(Coord::new(1, 1)), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 2x2 grid")]
+    fn indexing_off_the_grid_panics() {
+        let grid = Grid::new(2, 2, 0);
+        let _ = grid[Coord::new(0, 2)];
+    }
+}
@@ -0,0 +1,11 @@
+extern crate common;
+
+mod coord;
+mod direction;
+mod grid;
+mod point;
+
+pub use coord::Coord;
+pub use direction::Direction;
+pub use grid::Grid;
+pub use point::Point;
@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use common::{Answer, Json, ParseError};
+use common::parse::parse_pair;
+
+use coord::OFFSETS8;
+use direction::Direction;
+
+/// A position on an unbounded plane, with y growing downward; ordered in reading order.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Point {
+        Point{x, y}
+    }
+
+    pub fn step(self, direction: Direction) -> Point {
+        let (dy, dx) = direction.offset();
+        Point{x: self.x + dx, y: self.y + dy}
+    }
+
+    /// The four orthogonal neighbours, in reading order.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.iter().map(move |&d| self.step(d))
+    }
+
+    /// The eight surrounding points, in reading order.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        OFFSETS8.iter().map(move |&(dy, dx)| Point{x: self.x + dx, y: self.y + dy})
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The squared Euclidean distance, which orders points the same way as the distance itself.
+    pub fn squared_distance(self, other: Point) -> u64 {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        dx * dx + dy * dy
+    }
+}
+
+impl Ord for Point {
+    fn cmp(&self, other: &Point) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point{x: self.x + other.x, y: self.y + other.y}
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point{x: self.x - other.x, y: self.y - other.y}
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+/// Shown as `x,y`.
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Parses `x, y`, with any whitespace around either number.
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Point, ParseError> {
+        let (x, y) = parse_pair(s, s, ',', "<x>, <y>")?;
+        Ok(Point{x, y})
+    }
+}
+
+impl Answer for Point {
+    fn value(&self) -> Json {
+        Json::from(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(-2, 3), Point::new(4, -5));
+        assert_eq!(a.manhattan_distance(b), 14);
+        assert_eq!(a.chebyshev_distance(b), 8);
+        assert_eq!(a.squared_distance(b), 100);
+        assert_eq!(b.manhattan_distance(a), 14);
+        assert_eq!(a.manhattan_distance(a), 0);
+    }
+
+    #[test]
+    fn points_order_in_reading_order() {
+        let mut points = vec![Point::new(1, 1), Point::new(0, 2), Point::new(-1, 1), Point::new(5, 0)];
+        points.sort();
+        assert_eq!(points, vec![Point::new(5, 0), Point::new(-1, 1), Point::new(1, 1), Point::new(0, 2)]);
+    }
+
+    #[test]
+    fn neighbours_surround_the_point_in_reading_order() {
+        let p = Point::new(0, 0);
+        let neighbours: Vec<Point> = p.neighbours().collect();
+        assert_eq!(neighbours, vec![Point::new(0, -1), Point::new(-1, 0), Point::new(1, 0), Point::new(0, 1)]);
+
+        let surrounding: Vec<Point> = p.neighbours8().collect();
+        assert_eq!(surrounding.len(), 8);
+        assert!(surrounding.windows(2).all(|w| w[0] < w[1]));
+        assert!(surrounding.iter().all(|&q| p.chebyshev_distance(q) == 1));
+        assert_eq!(p.step(Direction::South) - p, Point::new(0, 1));
+    }
+
+    #[test]
+    fn parses_and_shows_coordinates() {
+        let p: Point = " -3,  7 ".parse().unwrap();
+        assert_eq!(p, Point::new(-3, 7));
+        assert_eq!(p.to_string(), "-3,7");
+        assert!("3; 7".parse::<Point>().is_err());
+
+        let mut q = p;
+        q += Point::new(3, -7);
+        assert_eq!(q, Point::default());
+        q -= p;
+        assert_eq!(q, Point::new(3, -7));
+    }
+}